@@ -1,7 +1,56 @@
-use std::env;
+use std::{env, fs, path::Path, process::Command};
 
 fn main() {
-    if let Ok(profile) = env::var("PROFILE") {
-        println!("cargo:rustc-cfg=profile={profile:?}");
+    println!("cargo:rustc-check-cfg=cfg(profile, values(any()))");
+    let profile = env::var("PROFILE").unwrap_or_else(|_| "unknown".into());
+    println!("cargo:rustc-cfg=profile={profile:?}");
+    println!("cargo:rustc-env=ADVENT_PROFILE={profile}");
+
+    let rustc = env::var("RUSTC").unwrap_or_else(|_| "rustc".into());
+    let version = Command::new(rustc)
+        .arg("--version")
+        .output()
+        .ok()
+        .and_then(|o| String::from_utf8(o.stdout).ok())
+        .unwrap_or_default();
+    println!("cargo:rustc-env=ADVENT_RUSTC_VERSION={}", version.trim());
+
+    let features = env::var("CARGO_CFG_TARGET_FEATURE").unwrap_or_default();
+    println!("cargo:rustc-env=ADVENT_TARGET_FEATURES={features}");
+
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .args(args)
+            .output()
+            .ok()
+            .filter(|o| o.status.success())
+            .and_then(|o| String::from_utf8(o.stdout).ok())
+            .map(|s| s.trim().to_owned())
+    };
+    if let Some(commit) = git(&["describe", "--always", "--dirty"]) {
+        println!("cargo:rustc-env=ADVENT_COMMIT={commit}");
+    }
+
+    // describe again when HEAD moves, the branch gets a commit, changes are
+    // staged or a day is edited
+    println!("cargo:rerun-if-changed=build.rs");
+    let branch = git(&["rev-parse", "--symbolic-full-name", "HEAD"]).filter(|b| b != "HEAD");
+    let files = ["HEAD", "index", "packed-refs"].map(str::to_owned);
+    for file in files.into_iter().chain(branch) {
+        let path = git(&["rev-parse", "--path-format=absolute", "--git-path", &file]);
+        if let Some(path) = path.filter(|p| Path::new(p).exists()) {
+            println!("cargo:rerun-if-changed={path}");
+        }
+    }
+    if let Some(Ok(entries)) = git(&["rev-parse", "--show-toplevel"]).map(fs::read_dir) {
+        for entry in entries.flatten() {
+            let is_year = entry
+                .file_name()
+                .to_str()
+                .is_some_and(|n| n.parse::<u32>().is_ok());
+            if is_year && entry.path().is_dir() {
+                println!("cargo:rerun-if-changed={}", entry.path().display());
+            }
+        }
     }
 }
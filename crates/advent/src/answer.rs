@@ -0,0 +1,90 @@
+use prelude::Grid;
+use std::fmt;
+
+use crate::ocr;
+
+/// The answer produced by a part.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "json", derive(serde::Serialize))]
+pub enum Answer {
+    Int(i128),
+    Str(String),
+    /// Rows of a rendered grid, usually letters drawn with `█`.
+    Grid(Vec<String>),
+}
+
+macro_rules! impl_from_int {
+    ($($t:ty),*) => {$(
+        impl From<$t> for Answer {
+            fn from(n: $t) -> Self {
+                Self::Int(n as i128)
+            }
+        }
+    )*};
+}
+
+impl_from_int!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, usize);
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        let trimmed = s.trim_end_matches('\n');
+        if trimmed.contains('\n') {
+            Self::Grid(trimmed.lines().map(str::to_owned).collect())
+        } else if trimmed.len() == s.len() {
+            Self::Str(s)
+        } else {
+            Self::Str(trimmed.to_owned())
+        }
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        s.to_owned().into()
+    }
+}
+
+impl From<Grid<bool>> for Answer {
+    fn from(grid: Grid<bool>) -> Self {
+        grid.pretty().into()
+    }
+}
+
+impl Answer {
+    /// Whether this is the answer recorded as `expected`, either exactly or,
+    /// for a grid, as the letters it draws.
+    pub fn matches(&self, expected: &str) -> bool {
+        let expected = expected.trim_end_matches('\n');
+        match self {
+            Self::Grid(rows) if ocr::read(rows).as_deref() == Some(expected.trim()) => true,
+            answer => answer.to_string().trim_end_matches('\n') == expected,
+        }
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Int(n) => fmt::Display::fmt(n, f),
+            Self::Str(s) => fmt::Display::fmt(s, f),
+            Self::Grid(rows) => fmt::Display::fmt(&rows.join("\n"), f),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn kinds() {
+        assert_eq!(Answer::from(42_i64), Answer::Int(42));
+        assert_eq!(Answer::from(u64::MAX), Answer::Int(u64::MAX as i128));
+        assert_eq!(Answer::from("abc"), Answer::Str("abc".into()));
+        assert_eq!(Answer::from("abc\n"), Answer::Str("abc".into()));
+        assert_eq!(
+            Answer::from("█ █\n █ \n".to_owned()),
+            Answer::Grid(vec!["█ █".into(), " █ ".into()])
+        );
+    }
+}
@@ -1,20 +1,59 @@
-use crate::{stats::Stats, summary::BenchSummary};
+use crate::{
+    machine::Machine,
+    stats::Stats,
+    summary::{BenchSummary, RepeatSummary, ScaleColumn, ScaleSummary},
+};
 use argh::FromArgs;
 use std::{
-    fmt::Display,
     hint,
-    panic::UnwindSafe,
+    num::NonZeroUsize,
+    panic::{AssertUnwindSafe, UnwindSafe},
+    path::PathBuf,
     time::{Duration, Instant},
 };
 use summary::{RunSummary, Summary};
 use yansi::Paint;
 
+pub use answer::Answer;
 pub use prelude;
 
+mod answer;
 mod human;
+mod machine;
+pub mod ocr;
 mod stats;
 mod summary;
 
+#[doc(hidden)]
+pub mod snapshot;
+
+/// Generates an `answers` test that checks each part against the answers
+/// recorded in `input/YYYY/DD.answers`.
+///
+/// ```ignore
+/// advent::answers!(default_input, 2023 / 01, part1, part2);
+/// ```
+#[macro_export]
+macro_rules! answers {
+    ($input:path, $year:literal / $day:literal, $($part:path),+ $(,)?) => {
+        #[test]
+        fn answers() {
+            let input = $input();
+            $crate::snapshot::check(
+                concat!(
+                    env!("CARGO_MANIFEST_DIR"),
+                    "/input/",
+                    stringify!($year),
+                    "/",
+                    stringify!($day),
+                    ".answers"
+                ),
+                &[$(&|| $crate::Answer::from($part(input.clone()))),+],
+            );
+        }
+    };
+}
+
 pub fn new<'a, F, I>(parse: F) -> Builder<'a, I>
 where
     F: Fn() -> I + UnwindSafe + 'a,
@@ -26,7 +65,7 @@ where
 }
 
 type FnParse<'a, I> = Box<dyn Fn() -> I + 'a>;
-type FnPart<'a, I> = Box<dyn Fn(I) -> Box<dyn Display + 'a> + UnwindSafe + 'a>;
+type FnPart<'a, I> = Box<dyn Fn(I) -> Answer + UnwindSafe + 'a>;
 
 pub struct Builder<'a, I> {
     parse: FnParse<'a, I>,
@@ -39,14 +78,14 @@ where
 {
     pub fn part<F, R>(self, f: F) -> Self
     where
-        R: Display + 'a,
+        R: Into<Answer> + 'a,
         F: Fn(I) -> R + UnwindSafe + 'a,
     {
         let parse = self.parse;
         let mut parts = self.parts;
         parts.push((
             format!("Part {}", parts.len() + 1),
-            Box::new(move |i| Box::new(f(i))),
+            Box::new(move |i| f(i).into()),
         ));
         Self { parse, parts }
     }
@@ -74,19 +113,7 @@ where
 
         let input = (parse)();
         for (name, f) in parts {
-            let input = input.clone();
-
-            let (result, time) = {
-                let start = Instant::now();
-                let result = std::panic::catch_unwind(move || f(input));
-                let elapsed = start.elapsed();
-                let result = match result {
-                    Ok(result) => result.to_string(),
-                    Err(_) => "🚨👻🚨".to_owned(),
-                };
-                (result, elapsed)
-            };
-
+            let (result, time) = run_part(&f, input.clone());
             runs.push(RunSummary {
                 name,
                 result,
@@ -97,10 +124,101 @@ where
         Summary::Run(runs)
     }
 
-    fn bench(self) -> Summary {
+    fn repeat(self, n: NonZeroUsize) -> Summary {
+        let Self { parse, parts } = self;
+        let mut repeats = Vec::new();
+
+        let input = (parse)();
+        for (name, f) in parts {
+            // no warm up here, this is meant to be quick
+            let mut result = None;
+            let mut times = Vec::new();
+            for _ in 0..n.get() {
+                let (r, time) = run_part(&f, input.clone());
+                times.push(time);
+                result = r;
+                if result.is_none() {
+                    break;
+                }
+            }
+
+            repeats.push(RepeatSummary {
+                name,
+                result,
+                samples: times.len(),
+                median: stats::median(&times),
+                min: times.iter().copied().min().unwrap_or_default(),
+            });
+        }
+
+        Summary::Repeat(repeats)
+    }
+
+    fn scale(self, samples: usize) -> Option<Summary> {
+        const FRACTIONS: [f64; 6] = [1.0 / 32.0, 1.0 / 16.0, 1.0 / 8.0, 0.25, 0.5, 1.0];
+
+        let Self { parse, parts } = self;
+        let mut lines = Vec::new();
+        let mut columns: Vec<_> = ["Parse".to_owned()]
+            .into_iter()
+            .chain(parts.iter().map(|(name, _)| name.clone()))
+            .map(|name| ScaleColumn {
+                name,
+                times: Vec::new(),
+                exponent: None,
+            })
+            .collect();
+
+        for fraction in FRACTIONS {
+            prelude::input::set_fraction(Some(fraction));
+            let parsed = best_of(samples, &parse);
+            // the input only gets truncated if it came from `include_input!`
+            let n = prelude::input::last_lines()?;
+            if lines.last() == Some(&n) {
+                continue;
+            }
+            lines.push(n);
+
+            let (input, time) = parsed.unzip();
+            columns[0].times.push(time);
+            for ((_, f), column) in parts.iter().zip(&mut columns[1..]) {
+                let time = input
+                    .as_ref()
+                    .and_then(|input| best_of(samples, || f(input.clone())))
+                    .map(|(_, time)| time);
+                column.times.push(time);
+            }
+        }
+        prelude::input::set_fraction(None);
+
+        for column in &mut columns {
+            let points: Vec<_> = lines
+                .iter()
+                .zip(&column.times)
+                .filter_map(|(&n, t)| Some((n as f64, t.as_ref()?.as_secs_f64())))
+                .collect();
+            column.exponent = stats::fit_exponent(&points);
+        }
+
+        Some(Summary::Scale(ScaleSummary { lines, columns }))
+    }
+
+    fn bench(self, pin: Option<usize>) -> Summary {
         let Self { parse, parts } = self;
         let mut benches = Vec::new();
 
+        let pinned = pin.filter(|&core| {
+            let pinned = machine::pin_to_core(core);
+            if !pinned {
+                eprintln!(
+                    "{}\n",
+                    Paint::yellow(format!("Note: failed to pin to core {core}")).bold()
+                );
+            }
+            pinned
+        });
+        let machine = Machine::detect(pinned);
+
         fn bench_with_input<F, I, O>(input: I, f: F) -> Stats
         where
             I: Clone,
@@ -141,11 +259,21 @@ where
             benches.push(BenchSummary { name, stats });
         }
 
-        Summary::Bench(benches)
+        Summary::Bench {
+            machine,
+            parts: benches,
+        }
     }
 
     pub fn cli(self) {
-        let Opt { bench, output } = argh::from_env();
+        let Opt {
+            bench,
+            pin,
+            scale,
+            repeat,
+            output,
+            json_file,
+        } = argh::from_env();
 
         let summary = if bench {
             if cfg!(not(profile = "release")) {
@@ -154,7 +282,20 @@ where
                     Paint::yellow("Note: using --bench without --release").bold()
                 );
             }
-            self.bench()
+            self.bench(pin)
+        } else if scale {
+            match self.scale(repeat.map_or(1, NonZeroUsize::get)) {
+                Some(summary) => summary,
+                None => {
+                    eprintln!(
+                        "{}",
+                        Paint::red("--scale needs the input to come from `include_input!`").bold()
+                    );
+                    std::process::exit(1);
+                }
+            }
+        } else if let Some(n) = repeat {
+            self.repeat(n)
         } else {
             self.run()
         };
@@ -164,7 +305,37 @@ where
             Output::Json => summary.print_json().expect("failed to print json"),
             _ => summary.print(),
         }
+
+        if let Some(path) = json_file {
+            #[cfg(feature = "json")]
+            summary.write_json(&path).expect("failed to write json");
+            #[cfg(not(feature = "json"))]
+            eprintln!("`--json-file {}` requires crate feature", path.display());
+        }
+    }
+}
+
+/// Runs a single part, returning `None` as the result if it panicked.
+fn run_part<I>(f: &FnPart<'_, I>, input: I) -> (Option<Answer>, Duration) {
+    let start = Instant::now();
+    let result = std::panic::catch_unwind(AssertUnwindSafe(|| f(input)));
+    let elapsed = start.elapsed();
+    (result.ok(), elapsed)
+}
+
+/// Returns the output and fastest time of `samples` runs of `f`, or `None` if
+/// it panicked.
+fn best_of<O>(samples: usize, f: impl Fn() -> O) -> Option<(O, Duration)> {
+    let mut best = None;
+    for _ in 0..samples.max(1) {
+        let start = Instant::now();
+        let output = std::panic::catch_unwind(AssertUnwindSafe(&f)).ok()?;
+        let elapsed = start.elapsed();
+        if best.as_ref().is_none_or(|(_, t)| elapsed < *t) {
+            best = Some((output, elapsed));
+        }
     }
+    best
 }
 
 /// Run the program.
@@ -174,9 +345,26 @@ struct Opt {
     #[argh(switch)]
     bench: bool,
 
+    /// pin the benchmark to this core
+    #[argh(option)]
+    pin: Option<usize>,
+
+    /// time parse and each part on growing prefixes of the input's lines
+    /// and estimate how they scale (takes the best of --repeat runs)
+    #[argh(switch)]
+    scale: bool,
+
+    /// run each part this many times and report the median and min
+    #[argh(option)]
+    repeat: Option<NonZeroUsize>,
+
     /// the output style (boring, festive, json)
     #[argh(option, default = "Output::Human")]
     output: Output,
+
+    /// also write the summary as json to this file
+    #[argh(option)]
+    json_file: Option<PathBuf>,
 }
 
 #[derive(Debug)]
@@ -0,0 +1,83 @@
+//! Details about the machine and build a benchmark ran with.
+
+use std::{fs, thread};
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "json", derive(serde::Serialize))]
+pub struct Machine {
+    pub cpu: Option<String>,
+    pub cores: usize,
+    pub governor: Option<String>,
+    pub rustc: String,
+    pub profile: String,
+    pub target_features: Vec<String>,
+    pub commit: Option<String>,
+    pub pinned: Option<usize>,
+}
+
+impl Machine {
+    pub fn detect(pinned: Option<usize>) -> Self {
+        let cpu = fs::read_to_string("/proc/cpuinfo").ok().and_then(|info| {
+            info.lines()
+                .filter_map(|l| l.split_once(':'))
+                .find(|(k, _)| matches!(k.trim(), "model name" | "Model" | "Hardware"))
+                .map(|(_, v)| v.trim().to_owned())
+        });
+        let governor = fs::read_to_string(format!(
+            "/sys/devices/system/cpu/cpu{}/cpufreq/scaling_governor",
+            pinned.unwrap_or(0)
+        ))
+        .ok()
+        .map(|g| g.trim().to_owned());
+        Self {
+            cpu,
+            cores: online_cores(),
+            governor,
+            rustc: env!("ADVENT_RUSTC_VERSION").to_owned(),
+            profile: env!("ADVENT_PROFILE").to_owned(),
+            target_features: env!("ADVENT_TARGET_FEATURES")
+                .split(',')
+                .filter(|f| !f.is_empty())
+                .map(str::to_owned)
+                .collect(),
+            commit: option_env!("ADVENT_COMMIT").map(str::to_owned),
+            pinned,
+        }
+    }
+}
+
+/// The number of cores online, which unlike `available_parallelism` isn't
+/// narrowed by pinning.
+#[cfg(target_os = "linux")]
+fn online_cores() -> usize {
+    // SAFETY: `sysconf` only reads a value.
+    match unsafe { libc::sysconf(libc::_SC_NPROCESSORS_ONLN) } {
+        online if online > 0 => online as usize,
+        _ => thread::available_parallelism().map_or(1, |n| n.get()),
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn online_cores() -> usize {
+    thread::available_parallelism().map_or(1, |n| n.get())
+}
+
+/// Pins the current thread to a single core, returning whether it worked.
+/// Cores that don't exist are refused rather than handed to the kernel.
+#[cfg(target_os = "linux")]
+pub fn pin_to_core(core: usize) -> bool {
+    if core >= libc::CPU_SETSIZE as usize || core >= online_cores() {
+        return false;
+    }
+    // SAFETY: `cpu_set_t` is plain data and the set outlives the call.
+    unsafe {
+        let mut set: libc::cpu_set_t = std::mem::zeroed();
+        libc::CPU_SET(core, &mut set);
+        libc::sched_setaffinity(0, std::mem::size_of::<libc::cpu_set_t>(), &set) == 0
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn pin_to_core(_core: usize) -> bool {
+    false
+}
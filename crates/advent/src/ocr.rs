@@ -0,0 +1,76 @@
+//! Read the letters Advent of Code puzzles draw on grids.
+
+/// The 4×6 glyphs used by Advent of Code, with `#` as a lit pixel.
+const GLYPHS: &[(char, [&str; 6])] = &[
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', [".###", "..#.", "..#.", "..#.", "..#.", ".###"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Y', ["#...", "#...", ".#.#", "..#.", "..#.", "..#."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+/// Reads the letters drawn in `rows`, where anything other than a space or
+/// `.` counts as lit. Returns `None` unless every letter is recognised.
+pub fn read(rows: &[String]) -> Option<String> {
+    let rows: Vec<Vec<bool>> = rows
+        .iter()
+        .map(|r| r.chars().map(|c| c != ' ' && c != '.').collect())
+        .collect();
+    if rows.len() != 6 {
+        return None;
+    }
+
+    let width = rows.iter().map(Vec::len).max()?;
+    let lit = |x: usize, y: usize| rows[y].get(x).copied().unwrap_or(false);
+
+    let mut letters = String::new();
+    for start in (0..width).step_by(5) {
+        let empty = (0..6).all(|y| (start..start + 4).all(|x| !lit(x, y)));
+        if empty {
+            continue;
+        }
+        let (letter, _) = GLYPHS.iter().find(|(_, glyph)| {
+            glyph.iter().enumerate().all(|(y, row)| {
+                row.chars()
+                    .enumerate()
+                    .all(|(dx, c)| (c == '#') == lit(start + dx, y))
+            })
+        })?;
+        letters.push(*letter);
+    }
+
+    (!letters.is_empty()).then_some(letters)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn letters() {
+        let rows = [
+            " ██  ████ ███  █  █ ███  ████ ███    ██ ███   ███ ",
+            "█  █ █    █  █ █  █ █  █    █ █  █    █ █  █ █    ",
+            "█  █ ███  ███  █  █ █  █   █  ███     █ █  █ █    ",
+            "████ █    █  █ █  █ ███   █   █  █    █ ███   ██  ",
+            "█  █ █    █  █ █  █ █    █    █  █ █  █ █       █ ",
+            "█  █ █    ███   ██  █    ████ ███   ██  █    ███  ",
+        ];
+        let rows: Vec<String> = rows.iter().map(|r| r.to_string()).collect();
+        assert_eq!(read(&rows).as_deref(), Some("AFBUPZBJPS"));
+        assert_eq!(read(&rows[..5]), None);
+    }
+}
@@ -0,0 +1,117 @@
+//! Snapshot tests for puzzle answers, see [`answers!`](crate::answers).
+
+use std::{
+    collections::BTreeMap,
+    env, fs,
+    panic::{self, AssertUnwindSafe},
+};
+
+use crate::Answer;
+
+/// Reads a snapshot file made up of `--- Part N ---` headers each followed by
+/// the answer for that part.
+fn read(path: &str) -> Option<BTreeMap<String, String>> {
+    let text = fs::read_to_string(path).ok()?;
+    let mut answers = BTreeMap::new();
+    let mut current: Option<(String, Vec<&str>)> = None;
+    for line in text.lines() {
+        let header = line
+            .strip_prefix("--- ")
+            .and_then(|l| l.strip_suffix(" ---"));
+        match header {
+            Some(name) => {
+                if let Some((name, lines)) = current.take() {
+                    answers.insert(name, lines.join("\n"));
+                }
+                current = Some((name.to_owned(), Vec::new()));
+            }
+            None => {
+                if let Some((_, lines)) = &mut current {
+                    lines.push(line);
+                }
+            }
+        }
+    }
+    if let Some((name, lines)) = current {
+        answers.insert(name, lines.join("\n"));
+    }
+    Some(answers)
+}
+
+fn write(path: &str, answers: &BTreeMap<String, String>) {
+    let text: String = answers
+        .iter()
+        .map(|(name, answer)| format!("--- {name} ---\n{}\n", answer.trim_end_matches('\n')))
+        .collect();
+    fs::write(path, text).unwrap_or_else(|e| panic!("failed to write `{path}`: {e}"));
+}
+
+/// Checks each part against the snapshot at `path`.
+///
+/// Parts without a recorded answer are skipped, unless `ADVENT_BLESS` is set
+/// in which case their current answer is recorded instead. Grids also match
+/// the letters they draw, which is what Advent of Code accepted.
+pub fn check(path: &str, parts: &[&dyn Fn() -> Answer]) {
+    let bless = env::var_os("ADVENT_BLESS").is_some();
+    let mut answers = match read(path) {
+        Some(answers) => answers,
+        None if bless => BTreeMap::new(),
+        None => panic!("no answers recorded at `{path}`, use `cargo advent test --bless`"),
+    };
+
+    let mut blessed = false;
+    for (i, part) in parts.iter().enumerate() {
+        let name = format!("Part {}", i + 1);
+        match answers.get(&name) {
+            Some(expected) => {
+                let actual = part();
+                assert!(
+                    actual.matches(expected),
+                    "{name} does not match the answer recorded at `{path}`\n  \
+                     actual: {actual}\nexpected: {expected}"
+                );
+            }
+            None if bless => match panic::catch_unwind(AssertUnwindSafe(part)) {
+                Ok(actual) => {
+                    answers.insert(name, actual.to_string());
+                    blessed = true;
+                }
+                Err(_) => eprintln!("not recording {name} because it panicked"),
+            },
+            None => {}
+        }
+    }
+
+    if blessed {
+        write(path, &answers);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seeded_grid() {
+        // `cargo advent test --bless` seeds the letters Advent of Code accepted
+        let path = env::temp_dir().join(format!("advent-snapshot-{}.answers", std::process::id()));
+        let path = path.to_str().unwrap();
+        fs::write(path, "--- Part 1 ---\n42\n--- Part 2 ---\nAU\n").unwrap();
+
+        let grid = Answer::from(
+            "\
+.##..#..#
+#..#.#..#
+#..#.#..#
+####.#..#
+#..#.#..#
+#..#..##."
+                .to_owned(),
+        );
+        check(path, &[&|| Answer::from(42), &|| grid.clone()]);
+
+        let wrong = panic::catch_unwind(|| check(path, &[&|| Answer::from(42), &|| "UA".into()]));
+        fs::remove_file(path).unwrap();
+        assert!(wrong.is_err());
+    }
+}
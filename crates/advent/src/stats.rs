@@ -48,6 +48,40 @@ pub fn basics(data: &[Duration]) -> Stats {
     }
 }
 
+pub fn median(data: &[Duration]) -> Duration {
+    let mut data = data.to_vec();
+    data.sort();
+
+    let mid = data.len() / 2;
+    match data.len() {
+        0 => Duration::ZERO,
+        len if len % 2 == 0 => (data[mid - 1] + data[mid]) / 2,
+        _ => data[mid],
+    }
+}
+
+/// Fits `y = c·x^k` to the points by least squares in log-log space and
+/// returns `k`.
+pub fn fit_exponent(points: &[(f64, f64)]) -> Option<f64> {
+    let logs: Vec<(f64, f64)> = points
+        .iter()
+        .filter(|&&(x, y)| x > 0.0 && y > 0.0)
+        .map(|&(x, y)| (x.ln(), y.ln()))
+        .collect();
+    if logs.len() < 2 {
+        return None;
+    }
+
+    let n = logs.len() as f64;
+    let mean_x = logs.iter().map(|(x, _)| x).sum::<f64>() / n;
+    let mean_y = logs.iter().map(|(_, y)| y).sum::<f64>() / n;
+    let (cov, var) = logs.iter().fold((0.0, 0.0), |(cov, var), (x, y)| {
+        let dx = x - mean_x;
+        (cov + dx * (y - mean_y), var + dx * dx)
+    });
+    (var > 0.0).then(|| cov / var)
+}
+
 pub fn percentile(data: &[f64], pct: f64) -> f64 {
     let zero: f64 = 0.0;
     let hundred: f64 = 100.0;
@@ -69,3 +103,17 @@ pub fn percentile(data: &[f64], pct: f64) -> f64 {
     let hi = data[n + 1];
     lo + (hi - lo) * d
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exponent() {
+        let linear: Vec<_> = (1..10).map(|x| (x as f64, 3.0 * x as f64)).collect();
+        let quadratic: Vec<_> = (1..10).map(|x| (x as f64, (x * x) as f64)).collect();
+        assert!((fit_exponent(&linear).unwrap() - 1.0).abs() < 1e-9);
+        assert!((fit_exponent(&quadratic).unwrap() - 2.0).abs() < 1e-9);
+        assert_eq!(fit_exponent(&[(1.0, 1.0)]), None);
+    }
+}
@@ -1,18 +1,28 @@
-use crate::{human, stats::Stats};
+use crate::{human, machine::Machine, stats::Stats, Answer};
 use std::time::Duration;
 use yansi::Paint;
 
 #[cfg_attr(feature = "json", derive(serde::Serialize))]
 pub enum Summary {
     Run(Vec<RunSummary>),
-    Bench(Vec<BenchSummary>),
+    Repeat(Vec<RepeatSummary>),
+    Scale(ScaleSummary),
+    Bench {
+        machine: Machine,
+        parts: Vec<BenchSummary>,
+    },
 }
 
 impl Summary {
     pub fn print(&self) {
         match self {
             Self::Run(runs) => print_run_summary(runs),
-            Self::Bench(benches) => print_bench_summary(benches),
+            Self::Repeat(repeats) => print_repeat_summary(repeats),
+            Self::Scale(scale) => print_scale_summary(scale),
+            Self::Bench { machine, parts } => {
+                print_machine(machine);
+                print_bench_summary(parts)
+            }
         }
     }
 
@@ -20,15 +30,29 @@ impl Summary {
     pub fn print_json(&self) -> serde_json::Result<()> {
         serde_json::to_writer(std::io::BufWriter::new(std::io::stdout()), self)
     }
+
+    #[cfg(feature = "json")]
+    pub fn write_json(&self, path: &std::path::Path) -> std::io::Result<()> {
+        let file = std::fs::File::create(path)?;
+        serde_json::to_writer(std::io::BufWriter::new(file), self)?;
+        Ok(())
+    }
 }
 
 #[cfg_attr(feature = "json", derive(serde::Serialize))]
 pub struct RunSummary {
     pub name: String,
-    pub result: String,
+    pub result: Option<Answer>,
     pub time: Duration,
 }
 
+fn display_result(result: &Option<Answer>) -> String {
+    match result {
+        Some(answer) => answer.to_string(),
+        None => "🚨👻🚨".to_owned(),
+    }
+}
+
 fn print_run_summary(parts: &[RunSummary]) {
     for (i, part) in parts.iter().enumerate() {
         let RunSummary { name, result, time, .. } = part;
@@ -40,18 +64,129 @@ fn print_run_summary(parts: &[RunSummary]) {
             "{}: {:>width$}\n{}",
             Paint::cyan(&name).bold(),
             Paint::fixed(245, format!("({time:?})")),
-            Paint::new(result).bold(),
+            Paint::new(display_result(result)).bold(),
             width = width
         )
     }
 }
 
+#[cfg_attr(feature = "json", derive(serde::Serialize))]
+pub struct RepeatSummary {
+    pub name: String,
+    pub result: Option<Answer>,
+    pub samples: usize,
+    pub median: Duration,
+    pub min: Duration,
+}
+
+fn print_repeat_summary(parts: &[RepeatSummary]) {
+    for (i, part) in parts.iter().enumerate() {
+        let RepeatSummary {
+            name,
+            result,
+            samples,
+            median,
+            min,
+        } = part;
+        if i != 0 {
+            println!();
+        }
+        let median = human::Time::new(median.as_secs_f64());
+        let min = human::Time::with_scale(min.as_secs_f64(), median.scale());
+        let timing = format!("(median {median}, min {min}, ×{samples})");
+        let width = 46_usize.saturating_sub(name.chars().count() + 2);
+        println!(
+            "{}: {:>width$}\n{}",
+            Paint::cyan(&name).bold(),
+            Paint::fixed(245, timing),
+            Paint::new(display_result(result)).bold(),
+            width = width
+        )
+    }
+}
+
+#[cfg_attr(feature = "json", derive(serde::Serialize))]
+pub struct ScaleSummary {
+    pub lines: Vec<usize>,
+    pub columns: Vec<ScaleColumn>,
+}
+
+#[cfg_attr(feature = "json", derive(serde::Serialize))]
+pub struct ScaleColumn {
+    pub name: String,
+    pub times: Vec<Option<Duration>>,
+    pub exponent: Option<f64>,
+}
+
+fn print_scale_summary(scale: &ScaleSummary) {
+    let ScaleSummary { lines, columns } = scale;
+
+    print!("{:>10}", Paint::new("Lines").bold());
+    for column in columns {
+        print!("{:>12}", Paint::cyan(&column.name).bold());
+    }
+    println!();
+
+    for (i, n) in lines.iter().enumerate() {
+        print!("{:>10}", n);
+        for column in columns {
+            match column.times[i] {
+                Some(time) => print!("{:>12}", human::Time::new(time.as_secs_f64()).to_string()),
+                None => print!("{:>12}", "🚨👻🚨"),
+            }
+        }
+        println!();
+    }
+
+    print!("{:>10}", Paint::new("Exponent").bold());
+    for column in columns {
+        match column.exponent {
+            Some(k) => print!("{:>12}", Paint::green(format!("n^{k:.2}")).bold()),
+            None => print!("{:>12}", Paint::fixed(245, "?")),
+        }
+    }
+    println!();
+}
+
 #[cfg_attr(feature = "json", derive(serde::Serialize))]
 pub struct BenchSummary {
     pub name: String,
     pub stats: Stats,
 }
 
+fn print_machine(machine: &Machine) {
+    let Machine {
+        cpu,
+        cores,
+        governor,
+        rustc,
+        profile,
+        target_features,
+        commit,
+        pinned,
+    } = machine;
+
+    let mut cpu = format!("{} ({cores} cores", cpu.as_deref().unwrap_or("unknown CPU"));
+    if let Some(governor) = governor {
+        cpu.push_str(&format!(", {governor}"));
+    }
+    if let Some(core) = pinned {
+        cpu.push_str(&format!(", pinned to {core}"));
+    }
+    cpu.push(')');
+
+    let mut build = format!("{rustc}, {profile}");
+    if !target_features.is_empty() {
+        build.push_str(&format!(", {} target features", target_features.len()));
+    }
+    if let Some(commit) = commit {
+        build.push_str(&format!(", {commit}"));
+    }
+
+    println!("{}", Paint::fixed(245, cpu));
+    println!("{}\n", Paint::fixed(245, build));
+}
+
 fn print_bench_summary(parts: &[BenchSummary]) {
     for (i, part) in parts.iter().enumerate() {
         let BenchSummary { name, stats } = part;
@@ -0,0 +1,218 @@
+//! Benchmark every day and collect the results into a table.
+
+use std::{fmt::Display, fs, process, time::Duration};
+
+use anyhow::Result;
+use regex_macro::regex;
+use yansi::Paint;
+
+use crate::{
+    cargo::{get_binaries, get_workspace_path},
+    human::{Samples, Time},
+    json::{BenchSummary, DayMeta, Stats, Summary},
+    load_metadata, print, profile, refresh_day_meta,
+    select::Selection,
+};
+
+const PART_WIDTH: usize = 36;
+const NAME_WIDTH: usize = 30;
+
+/// The totals of the mean times of the parts of a year.
+#[derive(Debug, Default)]
+struct Total {
+    mean: Duration,
+    variance: f64,
+    days: usize,
+}
+
+impl Total {
+    fn add(&mut self, stats: &Stats) {
+        self.mean += stats.mean;
+        self.variance += stats.std_dev.as_secs_f64().powi(2);
+        self.days += 1;
+    }
+}
+
+/// Formats a mean and standard deviation with the deviation in the same
+/// scale as the mean.
+fn mean_std_dev(mean: f64, std_dev: f64) -> String {
+    let mean = Time::new(mean);
+    let std_dev = Time::with_scale(std_dev, mean.scale());
+    format!("{mean} ± {std_dev}")
+}
+
+fn part(summary: Option<&BenchSummary>) -> String {
+    match summary {
+        Some(BenchSummary { stats, .. }) => {
+            let time = mean_std_dev(stats.mean.as_secs_f64(), stats.std_dev.as_secs_f64());
+            let samples = Samples::new(stats.samples).to_string();
+            let width = PART_WIDTH.saturating_sub(time.chars().count() + 1);
+            format!(
+                "{} {:>width$}",
+                Paint::new(time).bold(),
+                Paint::fixed(245, samples),
+                width = width
+            )
+        }
+        None => format!("{:width$}", "", width = PART_WIDTH),
+    }
+}
+
+fn total(total: &Total) -> String {
+    if total.days == 0 {
+        return format!("{:width$}", "", width = PART_WIDTH);
+    }
+    let time = mean_std_dev(total.mean.as_secs_f64(), total.variance.sqrt());
+    let days = match total.days {
+        1 => "1 day".to_owned(),
+        n => format!("{n} days"),
+    };
+    let width = PART_WIDTH.saturating_sub(time.chars().count() + 1);
+    format!(
+        "{} {:>width$}",
+        Paint::yellow(time).bold(),
+        Paint::fixed(245, days),
+        width = width
+    )
+}
+
+fn line(left: char, mid: char, right: char) -> String {
+    format!(
+        "{left}────────────{pc:─^n_width$}─{mid}─{pc:─^width$}─{mid}─{pc:─^width$}─{right}",
+        pc = "",
+        n_width = NAME_WIDTH,
+        width = PART_WIDTH
+    )
+}
+
+/// Benchmarks each day in turn, printing a row of the table as each one
+/// finishes, then saves the table to `target/advent/bench.txt`.
+pub fn bench_all(selection: &Selection, offline: bool, args: &[String]) -> Result<()> {
+    let binaries = get_binaries()?;
+    let binaries = selection.select(&binaries)?;
+
+    let mut metadata = load_metadata()?;
+
+    let mut success = true;
+    let mut table = Vec::new();
+    let mut emit = |line: String| {
+        println!("{line}");
+        table.push(line);
+    };
+
+    emit(line('╭', '┬', '╮'));
+    emit(format!(
+        "│ {:^n_width$} │ {:^width$} │ {:^width$} │",
+        Paint::new("Puzzle").bold(),
+        Paint::new("Part 1").bold(),
+        Paint::new("Part 2").bold(),
+        n_width = NAME_WIDTH + 11,
+        width = PART_WIDTH
+    ));
+    emit(line('├', '┼', '┤'));
+
+    let mut prev_year = 0;
+    let mut totals = [Total::default(), Total::default()];
+
+    let total_row = |year: u32, totals: &[Total; 2]| {
+        format!(
+            "│ {:<n_width$} │ {} │ {} │",
+            Paint::cyan(format!("{year:04} total")).bold(),
+            total(&totals[0]),
+            total(&totals[1]),
+            n_width = NAME_WIDTH + 11,
+        )
+    };
+
+    for bin in binaries {
+        let year: u32 = bin.chars().take(4).collect::<String>().parse()?;
+        let day: u32 = bin.chars().skip(4).collect::<String>().parse()?;
+
+        if year != prev_year && prev_year != 0 {
+            emit(line('├', '┼', '┤'));
+            emit(total_row(prev_year, &totals));
+            emit(line('├', '┼', '┤'));
+            totals = Default::default();
+        }
+        prev_year = year;
+
+        let output = process::Command::new(env!("CARGO"))
+            .args(["run", "--quiet", "--features", "json", "--release"])
+            .args(["--bin", bin, "--", "--bench", "--output", "json"])
+            .args(args)
+            .output()?;
+
+        let parts = match serde_json::from_slice(&output.stdout) {
+            Ok(Summary::Bench { parts }) if output.status.success() => Some(parts),
+            _ => None,
+        };
+
+        if !offline && !metadata.days.get(bin).is_some_and(DayMeta::has_all) {
+            refresh_day_meta(&mut metadata, year, day, profile::DEFAULT)?;
+        }
+
+        let mut puzzle_name = metadata
+            .days
+            .get(bin)
+            .and_then(|m| m.name.clone())
+            .unwrap_or_default();
+        if puzzle_name.len() > NAME_WIDTH {
+            puzzle_name = puzzle_name.chars().take(NAME_WIDTH - 1).collect();
+            puzzle_name.push('…');
+        };
+
+        let (part1, part2): (Box<dyn Display>, Box<dyn Display>) = match &parts {
+            Some(parts) => {
+                let find = |name: &str| parts.iter().find(|p| p.name == name);
+                for (total, name) in totals.iter_mut().zip(["Part 1", "Part 2"]) {
+                    if let Some(p) = find(name) {
+                        total.add(&p.stats);
+                    }
+                }
+                (
+                    Box::new(part(find("Part 1"))),
+                    Box::new(part(find("Part 2"))),
+                )
+            }
+            None => {
+                success = false;
+                let failed = format!("{:<width$}", "🚨👻🚨", width = PART_WIDTH - 3);
+                (
+                    Box::new(Paint::red(failed.clone())),
+                    Box::new(Paint::red(failed)),
+                )
+            }
+        };
+
+        emit(format!(
+            "│ {}: {:<n_width$} │ {} │ {} │",
+            Paint::cyan(format!("{year:04} / {day:02}")).bold(),
+            puzzle_name,
+            part1,
+            part2,
+            n_width = NAME_WIDTH,
+        ));
+    }
+
+    if prev_year != 0 {
+        emit(line('├', '┼', '┤'));
+        emit(total_row(prev_year, &totals));
+    }
+    emit(line('╰', '┴', '╯'));
+
+    let results_path = get_workspace_path().join("target/advent/bench.txt");
+    fs::create_dir_all(results_path.parent().unwrap())?;
+    let ansi_re = regex!(r"\x1b\[[0-9;]*m");
+    let table: String = table
+        .iter()
+        .map(|l| ansi_re.replace_all(l, "") + "\n")
+        .collect();
+    fs::write(&results_path, table)?;
+    print("Saved", format!("results to `{}`", results_path.display()));
+
+    if !success {
+        process::exit(1);
+    }
+
+    Ok(())
+}
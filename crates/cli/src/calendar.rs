@@ -0,0 +1,78 @@
+//! How many puzzles each event has and when they are released.
+
+use time::{macros::time, Date, Month, OffsetDateTime, Time, UtcOffset};
+
+/// The rules for the events from `since` until the next change.
+#[derive(Debug)]
+pub struct Calendar {
+    pub since: u32,
+    pub days: u32,
+    /// The time of day puzzles are released, in the configured timezone.
+    pub release: Time,
+    /// The days with only one part, whose second star is given for finishing.
+    pub single_part: &'static [u32],
+}
+
+const CALENDARS: &[Calendar] = &[
+    Calendar {
+        since: 2015,
+        days: 25,
+        release: time!(0:00),
+        single_part: &[25],
+    },
+    Calendar {
+        since: 2025,
+        days: 12,
+        release: time!(0:00),
+        single_part: &[12],
+    },
+];
+
+/// The rules for `year`.
+pub fn calendar(year: u32) -> &'static Calendar {
+    CALENDARS
+        .iter()
+        .rev()
+        .find(|c| c.since <= year)
+        .unwrap_or(&CALENDARS[0])
+}
+
+impl Calendar {
+    pub fn has_day(&self, day: u32) -> bool {
+        (1..=self.days).contains(&day)
+    }
+
+    pub fn parts(&self, day: u32) -> u8 {
+        match self.single_part.contains(&day) {
+            true => 1,
+            false => 2,
+        }
+    }
+}
+
+/// When a day's puzzle unlocks, if the event has that day.
+pub fn unlock_time(year: u32, day: u32, offset: UtcOffset) -> Option<OffsetDateTime> {
+    if !calendar(year).has_day(day) {
+        return None;
+    }
+    let date = Date::from_calendar_date(year.try_into().ok()?, Month::December, day as u8).ok()?;
+    Some(date.with_time(calendar(year).release).assume_offset(offset))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn calendars() {
+        assert_eq!(calendar(2015).days, 25);
+        assert_eq!(calendar(2024).parts(25), 1);
+        assert_eq!(calendar(2025).days, 12);
+        assert_eq!(calendar(2025).parts(12), 1);
+        assert_eq!(calendar(2030).parts(11), 2);
+        assert!(!calendar(2025).has_day(13));
+        assert!(unlock_time(2023, 25, UtcOffset::UTC).is_some());
+        assert_eq!(unlock_time(2023, 40, UtcOffset::UTC), None);
+        assert_eq!(unlock_time(2023, 257, UtcOffset::UTC), None);
+    }
+}
@@ -1,31 +1,51 @@
-use anyhow::Result;
+use anyhow::{bail, Context, Result};
+use regex_macro::regex;
 use serde::{Deserialize, Serialize};
-use std::{fs, path::PathBuf};
+use std::{
+    collections::HashMap,
+    env, fs,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+};
+use toml_edit::{value, ArrayOfTables, Document, Item};
+
+use crate::calendar::calendar;
 
 pub fn get_bin_name(year: u32, day: u32) -> String {
     format!("{year:04}{day:02}")
 }
 
+/// Splits a name like `202317` into its year and day.
+pub fn parse_bin_name(name: &str) -> Option<(u32, u32)> {
+    let caps = regex!(r"^(\d{4})(\d{2})$").captures(name)?;
+    let year = caps[1].parse().ok()?;
+    let day = caps[2].parse().ok()?;
+    (year >= 2015 && calendar(year).has_day(day)).then_some((year, day))
+}
+
+/// The workspace root, overridable with `ADVENT_WORKSPACE_DIR`.
 pub fn get_workspace_path() -> PathBuf {
-    PathBuf::from(env!("CARGO_WORKSPACE_DIR"))
+    match env::var_os("ADVENT_WORKSPACE_DIR") {
+        Some(dir) => PathBuf::from(dir),
+        None => PathBuf::from(env!("CARGO_WORKSPACE_DIR")),
+    }
 }
 
 pub fn get_manifest_path() -> PathBuf {
     get_workspace_path().join("Cargo.toml")
 }
 
+/// The path of a day's source relative to the workspace.
+pub fn get_relative_bin_path(year: u32, day: u32) -> String {
+    format!("{year:04}/{day:02}.rs")
+}
+
 pub fn get_bin_path(year: u32, day: u32) -> PathBuf {
-    get_workspace_path().join(format!("{year:04}/{day:02}.rs"))
+    get_workspace_path().join(get_relative_bin_path(year, day))
 }
 
 pub fn display_bin_path(year: u32, day: u32) -> String {
-    let workspace_path = get_manifest_path();
-    let bin_path = get_bin_path(year, day);
-    bin_path
-        .strip_prefix(&workspace_path)
-        .unwrap_or(&bin_path)
-        .display()
-        .to_string()
+    get_relative_bin_path(year, day)
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Deserialize, Serialize)]
@@ -36,38 +56,215 @@ pub struct Binary {
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Binaries {
+    #[serde(default)]
     pub bin: Vec<Binary>,
 }
 
-impl Binaries {
-    pub fn ensure_has(&mut self, year: u32, day: u32) -> bool {
-        let binary = Binary {
-            name: get_bin_name(year, day),
-            path: get_bin_path(year, day),
+/// Finds every day's solution, a `YYYY/DD.rs` file in the workspace, in
+/// name order.
+pub fn get_binaries() -> Result<Binaries> {
+    let mut bin = Vec::new();
+    for entry in fs::read_dir(get_workspace_path())? {
+        let dir = entry?;
+        let Some(year) = dir.file_name().to_str().and_then(|y| y.parse::<u32>().ok()) else {
+            continue;
         };
+        if !dir.file_type()?.is_dir() {
+            continue;
+        }
+        for entry in fs::read_dir(dir.path())? {
+            let file = entry?.file_name();
+            let Some(day) = file
+                .to_str()
+                .and_then(|f| f.strip_suffix(".rs"))
+                .filter(|d| d.len() == 2)
+                .and_then(|d| d.parse::<u32>().ok())
+            else {
+                continue;
+            };
+            let name = get_bin_name(year, day);
+            if parse_bin_name(&name).is_some() {
+                bin.push(Binary {
+                    name,
+                    path: get_relative_bin_path(year, day).into(),
+                });
+            }
+        }
+    }
+    bin.sort();
+    Ok(Binaries { bin })
+}
+
+/// The first line of every shim, so they can be told apart from binaries
+/// written by hand.
+const SHIM_HEADER: &str = "// Generated by `cargo advent` from";
+
+pub fn get_shim_path(name: &str) -> PathBuf {
+    get_workspace_path().join(format!("src/bin/{name}.rs"))
+}
+
+/// The source of the shim for `binary`. It is `include!`d so the day file
+/// can't have inner attributes or `//!` comments.
+pub fn shim(binary: &Binary) -> String {
+    let path = binary.path.display();
+    format!("{SHIM_HEADER} {path}, edit that instead.\ninclude!(\"../../{path}\");\n")
+}
+
+/// Writes the `src/bin` shim that lets Cargo find each day's binary and
+/// removes the shims of days that no longer exist.
+pub fn sync_bins() -> Result<()> {
+    let binaries = get_binaries()?;
+    let dir = get_workspace_path().join("src/bin");
+    fs::create_dir_all(&dir)?;
 
-        let added = !self.bin.contains(&binary);
-        self.bin.push(binary);
-        self.bin.sort();
-        self.bin.dedup();
-        added
+    for binary in &binaries.bin {
+        let path = get_shim_path(&binary.name);
+        let contents = shim(binary);
+        if fs::read_to_string(&path).ok().as_ref() != Some(&contents) {
+            fs::write(&path, contents)?;
+        }
     }
+
+    for entry in fs::read_dir(&dir)? {
+        let path = entry?.path();
+        let Some(name) = path.file_stem().and_then(|n| n.to_str()) else {
+            continue;
+        };
+        let generated = fs::read_to_string(&path).is_ok_and(|s| s.starts_with(SHIM_HEADER));
+        if generated && !binaries.bin.iter().any(|b| b.name == name) {
+            fs::remove_file(&path)?;
+        }
+    }
+    Ok(())
 }
 
-pub fn get_binaries() -> Result<Binaries> {
-    let manifest = fs::read_to_string(&get_manifest_path())?;
-    let index = manifest.find("[[bin]]").unwrap();
-    let (_, binaries) = manifest.split_at(index);
-    let binaries = toml::from_str(binaries)?;
+/// The `[[bin]]` entries written in the manifest.
+pub fn get_manifest_binaries() -> Result<Binaries> {
+    let manifest = fs::read_to_string(get_manifest_path())?;
+    let binaries = toml::from_str(&manifest)?;
     Ok(binaries)
 }
 
-pub fn write_binaries(bins: Binaries) -> Result<()> {
-    let binaries = toml::to_string(&bins)?;
-    let manifest = fs::read_to_string(&get_manifest_path())?;
-    let index = manifest.find("[[bin]]").unwrap();
-    let (main, _) = manifest.split_at(index);
-    fs::write(&get_manifest_path(), main.to_owned() + &binaries)?;
+/// The manifest, edited in place so its comments and formatting are kept.
+pub struct Manifest {
+    doc: Document,
+}
 
-    Ok(())
+impl Manifest {
+    pub fn load() -> Result<Self> {
+        let manifest = fs::read_to_string(get_manifest_path())?;
+        Ok(Self {
+            doc: manifest.parse()?,
+        })
+    }
+
+    pub fn save(&self) -> Result<()> {
+        fs::write(get_manifest_path(), self.doc.to_string())?;
+        Ok(())
+    }
+
+    /// The `[[bin]]` entries and the index of the one named `name`.
+    fn find(&mut self, name: &str) -> Result<Option<(&mut ArrayOfTables, usize)>> {
+        let Some(bins) = self.doc.get_mut("bin") else {
+            return Ok(None);
+        };
+        let bins = bins
+            .as_array_of_tables_mut()
+            .context("`bin` in the manifest is not an array of tables")?;
+        let index = bins
+            .iter()
+            .position(|t| t.get("name").and_then(Item::as_str) == Some(name));
+        Ok(index.map(|index| (bins, index)))
+    }
+
+    /// Removes a `[[bin]]` entry. Returns whether it was there.
+    pub fn remove(&mut self, name: &str) -> Result<bool> {
+        let Some((bins, index)) = self.find(name)? else {
+            return Ok(false);
+        };
+        bins.remove(index);
+        if bins.is_empty() {
+            self.doc.remove("bin");
+        }
+        Ok(true)
+    }
+
+    /// Points a `[[bin]]` entry at another source file.
+    pub fn set_path(&mut self, name: &str, path: &Path) -> Result<()> {
+        let (bins, index) = self
+            .find(name)?
+            .with_context(|| format!("There is no {name} binary in the manifest."))?;
+        let table = bins.get_mut(index).unwrap();
+        table.insert("path", value(path.display().to_string()));
+        Ok(())
+    }
+}
+
+/// A message from `cargo --message-format=json`.
+#[derive(Debug, Deserialize)]
+#[serde(tag = "reason", rename_all = "kebab-case")]
+enum Message {
+    CompilerArtifact {
+        target: Target,
+        executable: Option<PathBuf>,
+    },
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Debug, Deserialize)]
+struct Target {
+    name: String,
+    kind: Vec<String>,
+}
+
+/// Builds `bins` with `cargo <args>` and returns the executable produced for
+/// each bin that compiled. Compiler errors are printed as usual.
+pub fn build_bins(args: &[&str], bins: &[&String]) -> Result<HashMap<String, PathBuf>> {
+    if bins.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let mut cmd = Command::new(env!("CARGO"));
+    cmd.args(args)
+        .arg("--message-format=json-render-diagnostics");
+    for bin in bins {
+        cmd.args(["--bin", bin]);
+    }
+    let output = cmd
+        .current_dir(get_workspace_path())
+        .stderr(Stdio::inherit())
+        .output()?;
+
+    let mut executables = HashMap::new();
+    for line in output.stdout.split(|&b| b == b'\n') {
+        if let Ok(Message::CompilerArtifact {
+            target,
+            executable: Some(executable),
+        }) = serde_json::from_slice(line)
+        {
+            if target.kind.iter().any(|k| k == "bin") {
+                executables.insert(target.name, executable);
+            }
+        }
+    }
+
+    if executables.is_empty() && !output.status.success() {
+        bail!("Failed to build any binaries.");
+    }
+
+    Ok(executables)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bin_names() {
+        assert_eq!(parse_bin_name("202317"), Some((2023, 17)));
+        assert_eq!(parse_bin_name("201500"), None);
+        assert_eq!(parse_bin_name("2023-17"), None);
+        assert_eq!(parse_bin_name("20231"), None);
+    }
 }
@@ -0,0 +1,337 @@
+//! Settings from `advent.toml` in the workspace and the user's config
+//! directory, overridable with environment variables.
+
+use std::{
+    collections::BTreeMap, env, fmt, fs, path::PathBuf, str::FromStr, sync::OnceLock,
+    time::Duration,
+};
+
+use anyhow::{anyhow, bail, Context, Result};
+use serde::Deserialize;
+use time::UtcOffset;
+
+use crate::{cargo::get_workspace_path, print, select::parse_duration, warn};
+
+const DEFAULT_EDITOR: &str = "code";
+const DEFAULT_USER_AGENT: &str =
+    "https://github.com/Dragon-Hatcher/AdventOfCode danieldragonhatcher@gmail.com";
+const DEFAULT_TIMEZONE: UtcOffset = time::macros::offset!(-5);
+const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+const DEFAULT_REQUEST_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The contents of an `advent.toml`.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+struct File {
+    editor: Option<String>,
+    user_agent: Option<String>,
+    timezone: Option<String>,
+    year: Option<u32>,
+    template: Option<String>,
+    base_url: Option<String>,
+    request_interval: Option<String>,
+    profiles: BTreeMap<String, ProfileFile>,
+}
+
+/// A `[profiles.<name>]` table.
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct ProfileFile {
+    session: Option<String>,
+}
+
+/// Where a setting came from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    Default,
+    File(PathBuf),
+    Env(String),
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Default => write!(f, "default"),
+            Self::File(path) => write!(f, "{}", path.display()),
+            Self::Env(var) => write!(f, "${var}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Setting<T> {
+    pub value: T,
+    pub source: Source,
+}
+
+/// The resolved settings.
+#[derive(Debug, Clone)]
+pub struct Config {
+    /// The command used to open new solutions, empty to not open them.
+    pub editor: Setting<String>,
+    pub user_agent: Setting<String>,
+    /// The timezone puzzles are released at midnight in.
+    pub timezone: Setting<UtcOffset>,
+    /// The year used when `--year` isn't given, the current event if unset.
+    pub year: Setting<Option<u32>>,
+    /// The template used by `new` when `--template` isn't given.
+    pub template: Setting<Option<String>>,
+    pub base_url: Setting<String>,
+    /// The least time to leave between requests to Advent of Code.
+    pub request_interval: Setting<Duration>,
+    /// The session token of each profile other than the default one, which
+    /// uses `AOC_SESSION_ID`.
+    pub profiles: BTreeMap<String, Option<Setting<String>>>,
+}
+
+/// Parses an offset like `-5`, `+01:00` or `-03:30`.
+fn parse_offset(value: &str) -> Result<UtcOffset> {
+    let parse = || -> Option<UtcOffset> {
+        let (hours, minutes) = value.split_once(':').unwrap_or((value, "0"));
+        let hours: i8 = hours.trim_start_matches('+').parse().ok()?;
+        let minutes: i8 = minutes.parse().ok()?;
+        let minutes = if value.starts_with('-') {
+            -minutes
+        } else {
+            minutes
+        };
+        UtcOffset::from_hms(hours, minutes, 0).ok()
+    };
+    parse().with_context(|| format!("`{value}` is not a UTC offset like `-05:00`"))
+}
+
+/// The environment variable holding the session token of a profile.
+pub fn session_var(profile: &str) -> String {
+    format!(
+        "AOC_SESSION_ID_{}",
+        profile.to_ascii_uppercase().replace('-', "_")
+    )
+}
+
+/// The user's `advent.toml`, in `$XDG_CONFIG_HOME/advent` or
+/// `~/.config/advent`.
+fn user_path() -> Option<PathBuf> {
+    let dir = env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+    Some(dir.join("advent/advent.toml"))
+}
+
+fn read(path: Option<PathBuf>) -> Result<Option<(File, PathBuf)>> {
+    let Some(path) = path.filter(|p| p.exists()) else {
+        return Ok(None);
+    };
+    let contents = fs::read_to_string(&path)?;
+    let file = toml::from_str(&contents)
+        .with_context(|| format!("Failed to parse `{}`", path.display()))?;
+    Ok(Some((file, path)))
+}
+
+/// A setting from the environment or the first file that has it.
+fn resolve<T: FromStr>(
+    files: &[(File, PathBuf)],
+    var: &str,
+    field: impl Fn(&File) -> Option<T>,
+) -> Result<Option<Setting<T>>>
+where
+    T::Err: fmt::Display,
+{
+    if let Ok(value) = env::var(var) {
+        let value = value
+            .parse()
+            .map_err(|e| anyhow!("Invalid value in `{var}`: {e}"))?;
+        return Ok(Some(Setting {
+            value,
+            source: Source::Env(var.to_owned()),
+        }));
+    }
+    Ok(files.iter().find_map(|(file, path)| {
+        Some(Setting {
+            value: field(file)?,
+            source: Source::File(path.clone()),
+        })
+    }))
+}
+
+impl<T> Setting<T> {
+    fn or(setting: Option<Self>, default: T) -> Self {
+        setting.unwrap_or(Self {
+            value: default,
+            source: Source::Default,
+        })
+    }
+
+    fn optional(setting: Option<Self>) -> Setting<Option<T>> {
+        match setting {
+            Some(Self { value, source }) => Setting {
+                value: Some(value),
+                source,
+            },
+            None => Setting {
+                value: None,
+                source: Source::Default,
+            },
+        }
+    }
+}
+
+impl Config {
+    /// Reads the config files. Environment variables take precedence over
+    /// the workspace file, which takes precedence over the user's file.
+    pub fn load() -> Result<Self> {
+        let files: Vec<_> = [
+            read(Some(get_workspace_path().join("advent.toml")))?,
+            read(user_path())?,
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+
+        let timezone = match resolve(&files, "ADVENT_TIMEZONE", |f| f.timezone.clone())? {
+            Some(Setting::<String> { value, source }) => Setting {
+                value: parse_offset(&value)
+                    .with_context(|| format!("Invalid timezone in {source}"))?,
+                source,
+            },
+            None => Setting::or(None, DEFAULT_TIMEZONE),
+        };
+        let mut profiles = BTreeMap::new();
+        for (file, path) in &files {
+            if let Some(name) = ["default", "all"]
+                .iter()
+                .find(|n| file.profiles.contains_key(**n))
+            {
+                bail!(
+                    "The profile name `{name}` in `{}` is reserved.",
+                    path.display()
+                );
+            }
+            profiles.extend(file.profiles.keys().map(|name| (name.clone(), None)));
+        }
+        for (name, session) in &mut profiles {
+            let var = session_var(name);
+            *session = resolve(&files, &var, |f| {
+                f.profiles.get(name.as_str())?.session.clone()
+            })?;
+        }
+
+        let request_interval = match resolve(&files, "ADVENT_REQUEST_INTERVAL", |f| {
+            f.request_interval.clone()
+        })? {
+            Some(Setting::<String> { value, source }) => Setting {
+                value: parse_duration(&value)
+                    .map_err(|e| anyhow!("Invalid request interval in {source}: {e}"))?,
+                source,
+            },
+            None => Setting::or(None, DEFAULT_REQUEST_INTERVAL),
+        };
+        let base_url = Setting::or(
+            resolve(&files, "AOC_BASE_URL", |f| f.base_url.clone())?,
+            DEFAULT_BASE_URL.to_owned(),
+        );
+
+        Ok(Self {
+            editor: Setting::or(
+                resolve(&files, "ADVENT_EDITOR", |f| f.editor.clone())?,
+                DEFAULT_EDITOR.to_owned(),
+            ),
+            user_agent: Setting::or(
+                resolve(&files, "ADVENT_USER_AGENT", |f| f.user_agent.clone())?,
+                DEFAULT_USER_AGENT.to_owned(),
+            ),
+            timezone,
+            year: Setting::optional(resolve(&files, "ADVENT_YEAR", |f| f.year)?),
+            template: Setting::optional(resolve(&files, "ADVENT_TEMPLATE", |f| {
+                f.template.clone()
+            })?),
+            base_url: Setting {
+                value: base_url.value.trim_end_matches('/').to_owned(),
+                source: base_url.source,
+            },
+            request_interval,
+            profiles,
+        })
+    }
+}
+
+static CONFIG: OnceLock<Config> = OnceLock::new();
+
+/// Loads the config, failing if a file can't be read.
+pub fn init() -> Result<()> {
+    if CONFIG.get().is_none() {
+        _ = CONFIG.set(Config::load()?);
+    }
+    Ok(())
+}
+
+/// The config loaded by [`init`], loading it now if that hasn't happened.
+pub fn config() -> &'static Config {
+    CONFIG.get_or_init(|| Config::load().expect("failed to load the config"))
+}
+
+/// Prints the resolved settings and where they came from.
+pub fn show() -> Result<()> {
+    fn line<T: fmt::Display>(name: &str, setting: &Setting<T>) {
+        print(name, format!("{} ({})", setting.value, setting.source));
+    }
+    fn optional<T: fmt::Display>(name: &str, setting: &Setting<Option<T>>, unset: &str) {
+        match &setting.value {
+            Some(value) => print(name, format!("{value} ({})", setting.source)),
+            None => print(name, format!("{unset} ({})", setting.source)),
+        }
+    }
+
+    let Config {
+        editor,
+        user_agent,
+        timezone,
+        year,
+        template,
+        base_url,
+        request_interval,
+        profiles,
+    } = config();
+    match editor.value.as_str() {
+        "" => print("editor", format!("none ({})", editor.source)),
+        _ => line("editor", editor),
+    }
+    line("user-agent", user_agent);
+    line("timezone", timezone);
+    optional("year", year, "the current event");
+    optional("template", template, "templates/default.rs or built in");
+    line("base-url", base_url);
+    print(
+        "interval",
+        format!(
+            "{:?} between requests ({})",
+            request_interval.value, request_interval.source
+        ),
+    );
+    for (name, session) in profiles {
+        match session {
+            Some(session) => print(
+                "profile",
+                format!("{name} (session from {})", session.source),
+            ),
+            None => warn(
+                "profile",
+                format!("{name} has no session, set `{}`", session_var(name)),
+            ),
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn offsets() {
+        let offset = |s| parse_offset(s).ok();
+        assert_eq!(offset("-5"), UtcOffset::from_hms(-5, 0, 0).ok());
+        assert_eq!(offset("+01:00"), UtcOffset::from_hms(1, 0, 0).ok());
+        assert_eq!(offset("-03:30"), UtcOffset::from_hms(-3, -30, 0).ok());
+        assert_eq!(offset("EST"), None);
+    }
+}
@@ -0,0 +1,260 @@
+//! Check the manifest and workspace for problems that break other commands.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process,
+};
+
+use anyhow::Result;
+
+use crate::{
+    cargo::{
+        get_binaries, get_manifest_binaries, get_workspace_path, parse_bin_name, Binary, Manifest,
+    },
+    load_metadata, print, warn,
+};
+
+/// Something wrong with a binary.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Problem {
+    Name,
+    Absolute(Option<PathBuf>),
+    MissingSource,
+    Listed,
+    MissingInput,
+    MissingMetadata,
+    Unfinished,
+}
+
+impl Problem {
+    /// Whether the problem stops the binary from building or running.
+    fn is_error(&self) -> bool {
+        !matches!(self, Self::MissingMetadata | Self::Unfinished)
+    }
+}
+
+/// The relative path an absolute `path` should have been. Paths from another
+/// checkout keep their `YYYY/DD.rs` ending if that exists here.
+fn relative_path(path: &Path, workspace: &Path) -> Option<PathBuf> {
+    if let Ok(relative) = path.strip_prefix(workspace) {
+        return Some(relative.to_owned());
+    }
+    let components: Vec<_> = path.components().collect();
+    let tail: PathBuf = components[components.len().checked_sub(2)?..]
+        .iter()
+        .collect();
+    workspace.join(&tail).exists().then_some(tail)
+}
+
+/// Checks a binary that has a `[[bin]]` entry in the manifest, a `YYYY/DD.rs`
+/// file found in the workspace, or both.
+fn diagnose(
+    entry: Option<&Binary>,
+    found: Option<&Binary>,
+    workspace: &Path,
+    has_metadata: bool,
+) -> Vec<Problem> {
+    let mut problems = Vec::new();
+    let name = &entry.or(found).unwrap().name;
+
+    let source = match (entry, found) {
+        // an entry replaces the shim, usually with a path from another checkout
+        (Some(_), Some(found)) => {
+            problems.push(Problem::Listed);
+            found.path.clone()
+        }
+        (Some(entry), None) => {
+            if parse_bin_name(name).is_none() {
+                problems.push(Problem::Name);
+            }
+            if entry.path.is_absolute() {
+                let relative = relative_path(&entry.path, workspace);
+                problems.push(Problem::Absolute(relative.clone()));
+                relative.unwrap_or_else(|| entry.path.clone())
+            } else {
+                entry.path.clone()
+            }
+        }
+        (None, found) => found.unwrap().path.clone(),
+    };
+
+    match fs::read_to_string(workspace.join(source)) {
+        Ok(source) if source.contains("todo!(") => problems.push(Problem::Unfinished),
+        Ok(_) => {}
+        Err(_) => problems.push(Problem::MissingSource),
+    }
+
+    if let Some((year, day)) = parse_bin_name(name) {
+        let input = workspace.join(format!("input/{year:04}/{day:02}.txt"));
+        if !input.exists() {
+            problems.push(Problem::MissingInput);
+        }
+        if !has_metadata {
+            problems.push(Problem::MissingMetadata);
+        }
+    }
+
+    problems
+}
+
+/// Checks every binary in the manifest and every day found in the
+/// workspace. With `fix` set, absolute paths in the manifest are made
+/// relative and entries that hide a found day are removed.
+pub fn doctor(fix: bool) -> Result<()> {
+    let workspace = get_workspace_path();
+    let found = get_binaries()?;
+    let listed = get_manifest_binaries()?;
+    let mut manifest = Manifest::load()?;
+    let metadata = load_metadata()?;
+
+    let mut names: Vec<_> = found
+        .bin
+        .iter()
+        .chain(&listed.bin)
+        .map(|b| &b.name)
+        .collect();
+    names.sort();
+    names.dedup();
+
+    let mut errors = 0;
+    let mut warnings = 0;
+    let mut fixed = 0;
+
+    for name in &names {
+        let entry = listed.bin.iter().find(|b| &b.name == *name);
+        let problems = diagnose(
+            entry,
+            found.bin.iter().find(|b| &b.name == *name),
+            &workspace,
+            metadata.days.contains_key(*name),
+        );
+        let path = entry
+            .map(|e| e.path.display().to_string())
+            .unwrap_or_default();
+
+        for problem in problems {
+            let message = match &problem {
+                Problem::Name => "the name is not a year and day like `202317`".to_owned(),
+                Problem::Absolute(Some(relative)) if fix => {
+                    manifest.set_path(name, relative)?;
+                    fixed += 1;
+                    print(
+                        name,
+                        format!("rewrote `{path}` to `{}`", relative.display()),
+                    );
+                    continue;
+                }
+                Problem::Absolute(Some(relative)) => format!(
+                    "`{path}` is absolute, it should be `{}` (fix with --fix)",
+                    relative.display()
+                ),
+                Problem::Absolute(None) => {
+                    format!("`{path}` is absolute and not in this workspace")
+                }
+                Problem::MissingSource => format!("`{path}` does not exist"),
+                Problem::Listed if fix => {
+                    manifest.remove(name)?;
+                    fixed += 1;
+                    print(name, "removed its `[[bin]]` entry from the manifest");
+                    continue;
+                }
+                Problem::Listed => format!(
+                    "its `[[bin]]` entry for `{path}` hides the discovered one (fix with --fix)"
+                ),
+                Problem::MissingInput => {
+                    "the default input is missing and every profile needs it to build, \
+                     download it with `cargo advent run`"
+                        .to_owned()
+                }
+                Problem::MissingMetadata => {
+                    "there is no metadata, sync it with `cargo advent meta --refresh`".to_owned()
+                }
+                Problem::Unfinished => "the solution still has `todo!()` placeholders".to_owned(),
+            };
+            if problem.is_error() {
+                errors += 1;
+            } else {
+                warnings += 1;
+            }
+            warn(name, message);
+        }
+    }
+
+    if fixed > 0 {
+        manifest.save()?;
+    }
+
+    let plural = |n: usize, one: &str, many: &str| match n {
+        1 => format!("1 {one}"),
+        n => format!("{n} {many}"),
+    };
+    print(
+        "Checked",
+        format!(
+            "{}: {}, {}{}",
+            plural(names.len(), "binary", "binaries"),
+            plural(errors, "error", "errors"),
+            plural(warnings, "warning", "warnings"),
+            match fixed {
+                0 => String::new(),
+                n => format!(", {} fixed", plural(n, "entry", "entries")),
+            }
+        ),
+    );
+
+    if errors > 0 {
+        process::exit(1);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn relative_paths() {
+        let workspace = env!("CARGO_MANIFEST_DIR");
+        let workspace = Path::new(workspace);
+        assert_eq!(
+            relative_path(&workspace.join("src/main.rs"), workspace),
+            Some(PathBuf::from("src/main.rs"))
+        );
+        assert_eq!(
+            relative_path(Path::new("/Users/someone/advent/src/main.rs"), workspace),
+            Some(PathBuf::from("src/main.rs"))
+        );
+        assert_eq!(
+            relative_path(Path::new("/Users/someone/advent/2015/99.rs"), workspace),
+            None
+        );
+    }
+
+    #[test]
+    fn entries() {
+        let workspace = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let bin = |name: &str, path: &str| Binary {
+            name: name.to_owned(),
+            path: path.into(),
+        };
+
+        let entry = bin("tool", "/Users/someone/advent/src/main.rs");
+        assert_eq!(
+            diagnose(Some(&entry), None, workspace, false),
+            [Problem::Name, Problem::Absolute(Some("src/main.rs".into()))]
+        );
+
+        let entry = bin("201501", "2015/01.rs");
+        assert_eq!(
+            diagnose(Some(&entry), None, workspace, true),
+            [Problem::MissingSource, Problem::MissingInput]
+        );
+
+        let found = bin("201501", "src/main.rs");
+        assert_eq!(
+            diagnose(Some(&entry), Some(&found), workspace, true),
+            [Problem::Listed, Problem::MissingInput]
+        );
+    }
+}
@@ -0,0 +1,241 @@
+//! Requests to Advent of Code, spaced out and retried as its automation
+//! guidelines ask.
+
+use std::{
+    fs,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+    thread,
+    time::{Duration, Instant},
+};
+
+use anyhow::{anyhow, bail, Result};
+use reqwest::{
+    blocking::{Client, RequestBuilder},
+    cookie::Jar,
+    header::{self, HeaderMap},
+    redirect::Policy,
+    StatusCode, Url,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    cargo::get_workspace_path,
+    config::{config, session_var},
+    profile, warn,
+};
+
+/// How many times a request is retried after a server error.
+const RETRIES: u32 = 3;
+
+/// How long to wait before the first retry, doubling each time.
+const BACKOFF: Duration = Duration::from_millis(500);
+
+/// What Advent of Code sends for inputs that haven't unlocked yet.
+const LOCKED: &str = include_str!("error.txt");
+
+/// A client that sends the session cookie of `profile` to `url`. Redirects
+/// aren't followed so being sent to log in can be noticed.
+fn client(url: &Url, profile: &str) -> Result<Client> {
+    let cookie = format!("session={}", profile::session(profile)?);
+    let jar = Jar::default();
+    jar.add_cookie_str(&cookie, url);
+
+    Ok(reqwest::blocking::ClientBuilder::new()
+        .cookie_provider(Arc::new(jar))
+        .user_agent(&config().user_agent.value)
+        .redirect(Policy::none())
+        .build()?)
+}
+
+/// Waits until the configured interval has passed since the last request.
+fn throttle() {
+    static LAST: Mutex<Option<Instant>> = Mutex::new(None);
+
+    let mut last = LAST.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(last) = *last {
+        thread::sleep(
+            config()
+                .request_interval
+                .value
+                .saturating_sub(last.elapsed()),
+        );
+    }
+    *last = Some(Instant::now());
+}
+
+fn expired(profile: &str) -> anyhow::Error {
+    let var = match profile {
+        profile::DEFAULT => "AOC_SESSION_ID".to_owned(),
+        profile => session_var(profile),
+    };
+    anyhow!("The session for the `{profile}` profile has expired or is invalid, log in again and update `{var}`.")
+}
+
+struct Reply {
+    status: StatusCode,
+    headers: HeaderMap,
+    body: String,
+}
+
+/// Turns the ways Advent of Code reports a problem into errors.
+fn check(url: &Url, profile: &str, reply: Reply) -> Result<Reply> {
+    let Reply {
+        status, headers, ..
+    } = &reply;
+    let body = reply.body.trim();
+
+    if status.is_redirection() && *status != StatusCode::NOT_MODIFIED {
+        let location = headers
+            .get(header::LOCATION)
+            .and_then(|l| l.to_str().ok())
+            .unwrap_or_default();
+        if location.contains("/auth/login") {
+            return Err(expired(profile));
+        }
+        bail!("{url} redirected to `{location}`.");
+    }
+    if *status == StatusCode::NOT_FOUND && body == LOCKED.trim() {
+        bail!("The puzzle has not been unlocked yet.");
+    }
+    if status.is_client_error() && body.to_ascii_lowercase().contains("log in") {
+        return Err(expired(profile));
+    }
+    if !status.is_success() && !status.is_redirection() {
+        bail!(
+            "{url} returned {status}: {}",
+            body.lines().next().unwrap_or_default()
+        );
+    }
+    // an expired session still gets the puzzle, but logged out
+    if body.contains(r#"/auth/login""#) {
+        return Err(expired(profile));
+    }
+    Ok(reply)
+}
+
+/// Sends the request made by `build`, retrying with backoff after
+/// connection and server errors if `retry` is set.
+fn send(
+    url: &Url,
+    profile: &str,
+    retry: bool,
+    build: impl Fn(&Client) -> RequestBuilder,
+) -> Result<Reply> {
+    let client = client(url, profile)?;
+    let mut attempt = 0;
+    loop {
+        throttle();
+        let retries_left = retry && attempt < RETRIES;
+        let backoff = BACKOFF * 2u32.pow(attempt);
+        attempt += 1;
+
+        let response = match build(&client).send() {
+            Ok(response) => response,
+            Err(e) if retries_left && (e.is_connect() || e.is_timeout()) => {
+                warn("Retrying", format!("{url} after {e}"));
+                thread::sleep(backoff);
+                continue;
+            }
+            Err(e) => return Err(e.into()),
+        };
+
+        let status = response.status();
+        if retries_left && (status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS) {
+            let after = response
+                .headers()
+                .get(header::RETRY_AFTER)
+                .and_then(|a| a.to_str().ok()?.parse().ok())
+                .map(Duration::from_secs);
+            warn("Retrying", format!("{url} after {status}"));
+            thread::sleep(after.unwrap_or(backoff));
+            continue;
+        }
+
+        let headers = response.headers().clone();
+        let body = response.text()?;
+        return check(
+            url,
+            profile,
+            Reply {
+                status,
+                headers,
+                body,
+            },
+        );
+    }
+}
+
+/// Downloads `url` as `profile`.
+pub fn download(url: &str, profile: &str) -> Result<String> {
+    let url: Url = url.parse()?;
+    Ok(send(&url, profile, true, |client| client.get(url.clone()))?.body)
+}
+
+/// Posts a form to `url` as `profile`. Failed posts aren't retried since
+/// they may have been received.
+pub fn post(url: &str, profile: &str, form: &[(&str, &str)]) -> Result<String> {
+    let url: Url = url.parse()?;
+    Ok(send(&url, profile, false, |client| {
+        client.post(url.clone()).form(form)
+    })?
+    .body)
+}
+
+/// A page and the validators to ask whether it has changed.
+#[derive(Debug, Serialize, Deserialize)]
+struct CachedPage {
+    etag: Option<String>,
+    last_modified: Option<String>,
+    body: String,
+}
+
+fn page_path(url: &Url, profile: &str) -> PathBuf {
+    let name = url.path().trim_matches('/').replace('/', "-");
+    get_workspace_path().join(format!("target/advent/pages/{profile}/{name}.json"))
+}
+
+/// Downloads a puzzle page as `profile`, asking the server to skip sending
+/// it again if it hasn't changed since it was cached.
+pub fn download_page(url: &str, profile: &str) -> Result<String> {
+    let url: Url = url.parse()?;
+    let path = page_path(&url, profile);
+    let cached: Option<CachedPage> = fs::read(&path)
+        .ok()
+        .and_then(|json| serde_json::from_slice(&json).ok());
+
+    let reply = send(&url, profile, true, |client| {
+        let mut request = client.get(url.clone());
+        if let Some(cached) = &cached {
+            if let Some(etag) = &cached.etag {
+                request = request.header(header::IF_NONE_MATCH, etag);
+            }
+            if let Some(last_modified) = &cached.last_modified {
+                request = request.header(header::IF_MODIFIED_SINCE, last_modified);
+            }
+        }
+        request
+    })?;
+
+    if reply.status == StatusCode::NOT_MODIFIED {
+        if let Some(cached) = cached {
+            return Ok(cached.body);
+        }
+        bail!("{url} was not modified but it isn't cached.");
+    }
+
+    let header = |name| {
+        let value = reply.headers.get(name)?.to_str().ok()?;
+        Some(value.to_owned())
+    };
+    let page = CachedPage {
+        etag: header(header::ETAG),
+        last_modified: header(header::LAST_MODIFIED),
+        body: reply.body,
+    };
+    if page.etag.is_some() || page.last_modified.is_some() {
+        fs::create_dir_all(path.parent().unwrap())?;
+        fs::write(&path, serde_json::to_string(&page)?)?;
+    }
+    Ok(page.body)
+}
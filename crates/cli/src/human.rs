@@ -23,6 +23,10 @@ pub enum Scale {
 #[derive(Debug, Clone, Copy)]
 pub struct Time(Number);
 
+/// Represents the number of samples.
+#[derive(Debug, Clone, Copy)]
+pub struct Samples(Number);
+
 impl Number {
     pub fn new(v: f64) -> Self {
         let scales = [
@@ -59,6 +63,21 @@ impl Time {
     pub fn new(secs: f64) -> Self {
         Self(Number::new(secs))
     }
+
+    pub fn with_scale(secs: f64, scale: Scale) -> Self {
+        Self(Number::with_scale(secs, scale))
+    }
+
+    pub fn scale(&self) -> Scale {
+        let Self(Number(_, scale)) = self;
+        *scale
+    }
+}
+
+impl Samples {
+    pub fn new(n: usize) -> Self {
+        Self(Number::new(n as f64))
+    }
 }
 
 impl fmt::Display for Time {
@@ -91,6 +110,32 @@ impl fmt::Display for Time {
     }
 }
 
+impl fmt::Display for Samples {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Some examples of what we need to handle nicely
+        //
+        // - 11 samples
+        // - 1.234k samples
+        // - 10.48M samples
+        // - 123.1G samples
+        //
+        // Rules:
+        // - If very small < 1000 then use no precision
+        // - Right align number in a width of 5 characters and fill the space.
+
+        let &Self(Number(v, s)) = self;
+        let p = 4 - digits(v);
+        let (precision, v, suffix) = match s {
+            Scale::Unit => (if v < 1000.0 { 0 } else { p }, v, " samples"),
+            Scale::Kilo => (p, v, "k samples"),
+            Scale::Mega => (p, v, "M samples"),
+            Scale::Giga => (p, v, "G samples"),
+            _ => unreachable!(),
+        };
+        fmt::Display::fmt(&format!("{v:.precision$}{suffix}"), f)
+    }
+}
+
 fn digits(mut v: f64) -> usize {
     let mut n = 0;
     while v >= 1. {
@@ -127,4 +172,18 @@ mod tests {
             assert_eq!(Time::new(t).to_string(), expected);
         }
     }
+
+    #[test]
+    fn samples_display() {
+        let test_cases = [
+            (3, "3 samples"),
+            (11, "11 samples"),
+            (123, "123 samples"),
+            (1337, "1.337k samples"),
+            (23_123, "23.12k samples"),
+        ];
+        for (t, expected) in test_cases {
+            assert_eq!(Samples::new(t).to_string(), expected);
+        }
+    }
 }
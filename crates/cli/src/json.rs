@@ -1,10 +1,14 @@
-use std::{collections::HashMap, time::Duration};
+use std::{collections::HashMap, fmt, time::Duration};
+
+use time::OffsetDateTime;
+
+use advent::ocr;
+
+use crate::{calendar::calendar, cargo::parse_bin_name, profile};
 
 #[derive(Debug, Clone, Copy, serde::Deserialize)]
 pub struct Stats {
     pub samples: usize,
-    pub min: Duration,
-    pub max: Duration,
     pub mean: Duration,
     pub std_dev: Duration,
 }
@@ -12,16 +16,78 @@ pub struct Stats {
 #[derive(Debug, Clone, serde::Deserialize)]
 pub enum Summary {
     Run(Vec<RunSummary>),
-    Bench(Vec<BenchSummary>),
+    Repeat(Vec<RepeatSummary>),
+    Bench { parts: Vec<BenchSummary> },
+}
+
+#[derive(Debug, Clone, serde::Deserialize)]
+pub enum Answer {
+    Int(i128),
+    Str(String),
+    Grid(Vec<String>),
+}
+
+impl Answer {
+    /// A single line version of the answer for tables.
+    pub fn summary(&self) -> String {
+        match self {
+            Self::Int(n) => n.to_string(),
+            Self::Str(s) => s.clone(),
+            Self::Grid(rows) => match ocr::read(rows) {
+                Some(letters) => letters,
+                None => {
+                    let width = rows.iter().map(|r| r.chars().count()).max().unwrap_or(0);
+                    format!("{width}×{} grid", rows.len())
+                }
+            },
+        }
+    }
+
+    /// The answer as it should be submitted, if it can be read.
+    pub fn submission(&self) -> Option<String> {
+        match self {
+            Self::Int(n) => Some(n.to_string()),
+            Self::Str(s) => Some(s.trim().to_owned()),
+            Self::Grid(rows) => ocr::read(rows),
+        }
+    }
+
+    /// Whether this matches the answer accepted by Advent of Code, if that
+    /// can be determined.
+    pub fn check(&self, expected: &str) -> Option<bool> {
+        let expected = expected.trim();
+        match self {
+            Self::Int(n) => Some(expected.parse() == Ok(*n)),
+            Self::Str(s) => Some(s.trim() == expected),
+            Self::Grid(rows) => ocr::read(rows).map(|letters| letters == expected),
+        }
+    }
 }
 
 #[derive(Debug, Clone, serde::Deserialize)]
 pub struct RunSummary {
     pub name: String,
-    pub result: String,
+    pub result: Option<Answer>,
     pub time: Duration,
 }
 
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct RepeatSummary {
+    pub name: String,
+    pub result: Option<Answer>,
+    pub median: Duration,
+}
+
+impl From<RepeatSummary> for RunSummary {
+    fn from(repeat: RepeatSummary) -> Self {
+        Self {
+            name: repeat.name,
+            result: repeat.result,
+            time: repeat.median,
+        }
+    }
+}
+
 #[derive(Debug, Clone, serde::Deserialize)]
 pub struct BenchSummary {
     pub name: String,
@@ -31,19 +97,211 @@ pub struct BenchSummary {
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct DayMeta {
     pub name: Option<String>,
+    /// How many parts the puzzle has, the last day of an event has one.
+    #[serde(default = "two")]
+    pub parts: u8,
     pub answer1: Option<String>,
     pub answer2: Option<String>,
+    #[serde(default)]
+    pub wrong: Vec<WrongAnswer>,
+    /// When `wait` saw the puzzle unlock, to time the solve from.
+    #[serde(
+        default,
+        with = "time::serde::rfc3339::option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub started: Option<OffsetDateTime>,
+}
+
+/// An answer Advent of Code rejected.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct WrongAnswer {
+    pub part: u8,
+    pub answer: String,
+    pub hint: Option<Hint>,
+    #[serde(with = "time::serde::rfc3339")]
+    pub time: OffsetDateTime,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum Hint {
+    TooHigh,
+    TooLow,
+}
+
+/// Why an answer is already known to be wrong.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rejection {
+    Rejected,
+    NotBelow(i128),
+    NotAbove(i128),
+}
+
+impl fmt::Display for Rejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Rejected => write!(f, "was already rejected"),
+            Self::NotBelow(bound) => write!(f, "is not below {bound} which was too high"),
+            Self::NotAbove(bound) => write!(f, "is not above {bound} which was too low"),
+        }
+    }
+}
+
+fn two() -> u8 {
+    2
+}
+
+impl Default for DayMeta {
+    fn default() -> Self {
+        Self {
+            name: None,
+            parts: two(),
+            answer1: None,
+            answer2: None,
+            wrong: Vec::new(),
+            started: None,
+        }
+    }
 }
 
 impl DayMeta {
+    /// Nothing known yet about a day but how many parts it has.
+    pub fn for_day(year: u32, day: u32) -> Self {
+        Self {
+            parts: calendar(year).parts(day),
+            ..Default::default()
+        }
+    }
+
+    /// Whether the title and the answer to every part are known.
     pub fn has_all(&self) -> bool {
-        self.name.is_some()
-            && self.answer1.is_some()
-            && (self.answer2.is_some() || self.name.as_ref().unwrap().ends_with("25"))
+        self.name.is_some() && self.answer1.is_some() && (self.answer2.is_some() || self.parts == 1)
+    }
+
+    /// Checks `answer` for `part` against the answers already rejected.
+    pub fn rejection(&self, part: u8, answer: &str) -> Option<Rejection> {
+        let answer = answer.trim();
+        let wrong: Vec<_> = self.wrong.iter().filter(|w| w.part == part).collect();
+        if wrong.iter().any(|w| w.answer == answer) {
+            return Some(Rejection::Rejected);
+        }
+
+        let n: i128 = answer.parse().ok()?;
+        let bound = |hint| {
+            wrong
+                .iter()
+                .filter(move |w| w.hint == Some(hint))
+                .filter_map(|w| w.answer.parse::<i128>().ok())
+        };
+        if let Some(high) = bound(Hint::TooHigh).min().filter(|&high| n >= high) {
+            return Some(Rejection::NotBelow(high));
+        }
+        if let Some(low) = bound(Hint::TooLow).max().filter(|&low| n <= low) {
+            return Some(Rejection::NotAbove(low));
+        }
+        None
     }
 }
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct AllMetadata {
     pub days: HashMap<String, DayMeta>,
+    /// The days as seen by each profile other than the default one, since
+    /// every account has its own answers.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub profiles: HashMap<String, HashMap<String, DayMeta>>,
+}
+
+impl AllMetadata {
+    /// The days as seen by `profile`.
+    pub fn days(&self, profile: &str) -> Option<&HashMap<String, DayMeta>> {
+        match profile {
+            profile::DEFAULT => Some(&self.days),
+            profile => self.profiles.get(profile),
+        }
+    }
+
+    pub fn days_mut(&mut self, profile: &str) -> &mut HashMap<String, DayMeta> {
+        match profile {
+            profile::DEFAULT => &mut self.days,
+            profile => self.profiles.entry(profile.to_owned()).or_default(),
+        }
+    }
+
+    pub fn day(&self, profile: &str, bin_name: &str) -> Option<&DayMeta> {
+        self.days(profile)?.get(bin_name)
+    }
+
+    /// Takes the number of parts of every day from the calendar, since
+    /// metadata cached before they were kept says every day has two.
+    pub fn fix_parts(&mut self) {
+        let profiles = self.profiles.values_mut();
+        for (bin_name, meta) in profiles.chain([&mut self.days]).flatten() {
+            if let Some((year, day)) = parse_bin_name(bin_name) {
+                meta.parts = calendar(year).parts(day);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wrong(part: u8, answer: &str, hint: Option<Hint>) -> WrongAnswer {
+        WrongAnswer {
+            part,
+            answer: answer.to_owned(),
+            hint,
+            time: OffsetDateTime::UNIX_EPOCH,
+        }
+    }
+
+    #[test]
+    fn rejections() {
+        let meta = DayMeta {
+            wrong: vec![
+                wrong(1, "100", Some(Hint::TooHigh)),
+                wrong(1, "50", Some(Hint::TooLow)),
+                wrong(1, "70", None),
+                wrong(2, "abc", None),
+            ],
+            ..Default::default()
+        };
+        assert_eq!(meta.rejection(1, "70"), Some(Rejection::Rejected));
+        assert_eq!(meta.rejection(1, "120"), Some(Rejection::NotBelow(100)));
+        assert_eq!(meta.rejection(1, "50"), Some(Rejection::Rejected));
+        assert_eq!(meta.rejection(1, "12"), Some(Rejection::NotAbove(50)));
+        assert_eq!(meta.rejection(1, "75"), None);
+        assert_eq!(meta.rejection(2, "abc"), Some(Rejection::Rejected));
+        assert_eq!(meta.rejection(2, "120"), None);
+    }
+
+    #[test]
+    fn completeness() {
+        let meta = DayMeta {
+            name: Some("Day 25: Full of Hot Air".to_owned()),
+            answer1: Some("2=-1=0".to_owned()),
+            ..DayMeta::for_day(2022, 25)
+        };
+        assert_eq!(meta.parts, 1);
+        assert!(meta.has_all());
+
+        // metadata from before parts were kept
+        let mut metadata: AllMetadata = serde_json::from_str(
+            r#"{"days": {
+                "202224": {"name": "Day 24", "answer1": "1", "answer2": null},
+                "202225": {"name": "Day 25", "answer1": "1", "answer2": null}
+            }, "profiles": {"alice": {
+                "202512": {"name": "Day 12", "answer1": "1", "answer2": null}
+            }}}"#,
+        )
+        .unwrap();
+        metadata.fix_parts();
+        assert_eq!(metadata.days["202224"].parts, 2);
+        assert!(!metadata.days["202224"].has_all());
+        assert_eq!(metadata.days["202225"].parts, 1);
+        assert!(metadata.days["202225"].has_all());
+        assert_eq!(metadata.day("alice", "202512").unwrap().parts, 1);
+    }
 }
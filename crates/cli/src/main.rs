@@ -1,20 +1,31 @@
 use std::{
+    collections::HashMap,
     env,
     fmt::Display,
     fs::{self},
-    io::{self, IsTerminal},
-    path::PathBuf,
-    process::{self, Output},
-    sync::Arc,
+    io::{self, BufRead, BufReader, IsTerminal},
+    num::NonZeroUsize,
+    path::{Path, PathBuf},
+    process::{self, Output, Stdio},
+    sync::{mpsc, Arc, Mutex},
+    thread,
+    time::Duration,
 };
 
-use anyhow::{bail, Context, Result};
+use anyhow::{anyhow, bail, Context, Result};
 use argh::FromArgs;
-use cargo::{display_bin_path, get_bin_name, get_bin_path, get_binaries, write_binaries};
-use json::{AllMetadata, DayMeta};
+use calendar::calendar;
+use cargo::{
+    build_bins, display_bin_path, get_bin_name, get_bin_path, get_binaries, get_shim_path,
+    get_workspace_path, parse_bin_name, sync_bins,
+};
+use config::config;
+use json::{AllMetadata, Answer, DayMeta, Rejection};
+use puzzle::Example;
 use regex_macro::regex;
-use reqwest::{cookie::Jar, Url};
-use time::{OffsetDateTime, UtcOffset};
+use scaffold::Shape;
+use select::{load_last_runs, parse_duration, write_last_runs, Days, LastRun, Selection, Status};
+use time::OffsetDateTime;
 use yansi::Paint;
 
 use crate::{
@@ -22,27 +33,120 @@ use crate::{
     json::{RunSummary, Summary},
 };
 
+mod bench;
+mod calendar;
 mod cargo;
+mod config;
+mod doctor;
+mod http;
 mod human;
 mod json;
+mod meta;
+mod profile;
+mod puzzle;
+mod read;
+mod scaffold;
+mod select;
+mod submit;
+mod test;
+#[cfg(test)]
+mod tests;
+mod wait;
 
 /// 🎄 Festive Advent of Code solution management modified from rossmacarthur/advent
 #[derive(Debug, FromArgs)]
 #[argh(example = "cargo advent -y 2022 -d 17")]
 struct Opt {
-    /// the puzzle year
+    /// the puzzle year, defaults to the configured year or the current
+    /// event
     #[argh(option, short = 'y')]
     year: Option<u32>,
 
-    /// the puzzle day
+    /// the puzzle day, or an inclusive range of days like 5..12 with --all
+    /// or meta
     #[argh(option, short = 'd')]
-    day: Option<u32>,
+    day: Option<Days>,
 
     /// run on all applicable days
     #[argh(switch, short = 'a')]
     all: bool,
 
-    /// the subcommand: run
+    /// run each part this many times and report the median (run only)
+    #[argh(option)]
+    repeat: Option<NonZeroUsize>,
+
+    /// only these bins, by name or comma separated (--all only)
+    #[argh(option)]
+    bin: Vec<String>,
+
+    /// only days with a wrong answer in the last run (--all only)
+    #[argh(switch)]
+    wrong: bool,
+
+    /// only days with an unchecked answer in the last run (--all only)
+    #[argh(switch)]
+    unknown: bool,
+
+    /// only days with a part slower than this in the last run, like 100ms
+    /// (--all only)
+    #[argh(option, from_str_fn(parse_duration))]
+    slower: Option<Duration>,
+
+    /// only days whose files changed since this git ref (--all only)
+    #[argh(option)]
+    since: Option<String>,
+
+    /// only use cached metadata to check answers (run and bench --all only)
+    #[argh(switch)]
+    offline: bool,
+
+    /// fetch the metadata or puzzle again even if it is cached (meta and
+    /// read only)
+    #[argh(switch)]
+    refresh: bool,
+
+    /// write the puzzle description to this file as Markdown (read only)
+    #[argh(option)]
+    markdown: Option<PathBuf>,
+
+    /// the account to use, one of the profiles in advent.toml or `all` to
+    /// check every one with run --all (run, bench, submit, meta and new)
+    #[argh(option)]
+    profile: Option<String>,
+
+    /// the template in templates/ to start from, overrides the configured
+    /// one (new only)
+    #[argh(option)]
+    template: Option<String>,
+
+    /// run this many days at once, defaults to 1 (run --all only)
+    #[argh(option, short = 'j')]
+    jobs: Option<usize>,
+
+    /// the bin to rename to, like 202318 (rename only)
+    #[argh(option)]
+    to: Option<String>,
+
+    /// remove `[[bin]]` entries that hide discovered days from the manifest
+    /// (doctor only)
+    #[argh(switch)]
+    fix: bool,
+
+    /// record the current answers as the expected ones (test only)
+    #[argh(switch)]
+    bless: bool,
+
+    /// delete the day's solution, which also needs an explicit --day
+    /// (remove only)
+    #[argh(switch)]
+    force: bool,
+
+    /// the part to submit, defaults to the first unsolved one (submit only)
+    #[argh(option, short = 'p')]
+    part: Option<u8>,
+
+    /// the subcommand: run, test, bench, new, remove, rename, wait, submit,
+    /// meta, read, config, doctor
     #[argh(positional)]
     command: Command,
 
@@ -56,6 +160,14 @@ enum Command {
     Test,
     Bench,
     New,
+    Remove,
+    Rename,
+    Wait,
+    Submit,
+    Meta,
+    Read,
+    Config,
+    Doctor,
 }
 
 impl argh::FromArgValue for Command {
@@ -65,16 +177,25 @@ impl argh::FromArgValue for Command {
             "test" => Ok(Self::Test),
             "bench" => Ok(Self::Bench),
             "new" => Ok(Self::New),
-            _ => Err("expected one of: run".into()),
+            "remove" => Ok(Self::Remove),
+            "rename" => Ok(Self::Rename),
+            "wait" => Ok(Self::Wait),
+            "submit" => Ok(Self::Submit),
+            "meta" => Ok(Self::Meta),
+            "read" => Ok(Self::Read),
+            "config" => Ok(Self::Config),
+            "doctor" => Ok(Self::Doctor),
+            _ => Err(
+                "expected one of: run, test, bench, new, remove, rename, wait, submit, \
+                meta, read, config, doctor"
+                    .into(),
+            ),
         }
     }
 }
 
-const RELEASE_TIMEZONE: UtcOffset = time::macros::offset!(-5);
-const MAX_DAY: u32 = 25;
-
 fn current_year() -> u32 {
-    let now = OffsetDateTime::now_utc().to_offset(RELEASE_TIMEZONE);
+    let now = OffsetDateTime::now_utc().to_offset(config().timezone.value);
 
     use time::Month as M;
     match now.month() {
@@ -83,20 +204,20 @@ fn current_year() -> u32 {
     }
 }
 
+/// The latest released day of the current event, or its last day if none
+/// have been released yet.
 fn current_day() -> u32 {
-    let now = OffsetDateTime::now_utc().to_offset(RELEASE_TIMEZONE);
-
-    use time::Month as M;
-    match now.month() {
-        M::December => (now.day() as u32).clamp(0, MAX_DAY),
-        _ => MAX_DAY,
-    }
+    let year = current_year();
+    let days = calendar(year).days;
+    (1..=days)
+        .rev()
+        .find(|&day| has_occurred(year, day))
+        .unwrap_or(days)
 }
 
 fn has_occurred(year: u32, day: u32) -> bool {
-    let cur_year = current_year();
-    let cur_day = current_day();
-    year < cur_year || (year == cur_year && day <= cur_day)
+    calendar::unlock_time(year, day, config().timezone.value)
+        .is_some_and(|unlock| unlock <= OffsetDateTime::now_utc())
 }
 
 fn main() -> Result<()> {
@@ -104,22 +225,111 @@ fn main() -> Result<()> {
         year,
         day,
         all,
+        bin,
+        wrong,
+        unknown,
+        slower,
+        since,
+        offline,
+        refresh,
+        markdown,
+        profile,
+        template,
+        repeat,
+        jobs,
+        to,
+        fix,
+        bless,
+        force,
+        part,
         command,
         args,
     } = argh::from_env();
 
-    let f_year = year.unwrap_or(current_year());
-    let f_day = day.unwrap_or(current_day());
+    config::init()?;
+
+    let ranged = all || matches!(command, Command::Meta);
+    if !ranged && day.is_some_and(|d| d.single().is_none()) {
+        bail!("A range of days can only be used with --all or meta.");
+    }
+
+    let profile = profile.as_deref().unwrap_or(profile::DEFAULT);
+    if profile == profile::ALL {
+        if !(all && matches!(command, Command::Run)) {
+            bail!("The `all` profile can only be used with run --all.");
+        }
+    } else {
+        profile::check(profile)?;
+    }
+
+    // days are found by their source files, so the shims are kept in step
+    // before anything is built
+    if matches!(
+        command,
+        Command::Run | Command::Test | Command::Bench | Command::Submit
+    ) {
+        sync_bins()?;
+    }
+
+    let f_year = year.or(config().year.value).unwrap_or(current_year());
+    let f_day = day.and_then(|d| d.single()).unwrap_or(current_day());
+    let selection = Selection {
+        year,
+        days: day,
+        bins: bin,
+        wrong,
+        unknown,
+        slower,
+        since,
+    };
 
     match (command, all) {
-        (Command::Run, false) => run(f_year, f_day, &args),
-        (Command::Run, true) => run_all(year, &args),
-        (Command::Test, false) => test(f_year, f_day, &args),
-        (Command::Test, true) => bail!("The --all flag cannot be used with test."),
-        (Command::Bench, false) => bench(f_year, f_day, &args),
-        (Command::Bench, true) => bail!("The --all flag cannot be used with bench."),
-        (Command::New, false) => new(f_year, f_day),
+        (Command::Run, false) => run(f_year, f_day, profile, repeat, &args),
+        (Command::Run, true) => run_all(&selection, profile, repeat, jobs, offline, &args),
+        (Command::Test, false) => test(f_year, f_day, bless, &args),
+        (Command::Test, true) => test::test_all(&selection, &args),
+        (Command::Bench, false) => bench(f_year, f_day, profile, &args),
+        (Command::Bench, true) => bench::bench_all(&selection, offline, &args),
+        (Command::New, false) => new(
+            f_year,
+            f_day,
+            profile,
+            template.as_deref().or(config().template.value.as_deref()),
+        ),
         (Command::New, true) => bail!("The --all flag cannot be used with new."),
+        (Command::Remove, false) => match (day, force) {
+            (Some(_), true) => remove(f_year, f_day),
+            _ if !get_bin_path(f_year, f_day).exists() => remove(f_year, f_day),
+            _ => bail!(
+                "Removing {} deletes `{}`, confirm with `-d {f_day} --force`.",
+                get_bin_name(f_year, f_day),
+                display_bin_path(f_year, f_day)
+            ),
+        },
+        (Command::Remove, true) => bail!("The --all flag cannot be used with remove."),
+        (Command::Rename, false) => match to {
+            Some(to) => rename(f_year, f_day, &to),
+            None => bail!("Give the bin to rename to with --to."),
+        },
+        (Command::Rename, true) => bail!("The --all flag cannot be used with rename."),
+        (Command::Wait, false) => match day.map(|d| d.single()) {
+            Some(None) => bail!("Give a single day to wait for, or none for the next one."),
+            day => wait::wait(
+                &wait::SystemClock,
+                year.or(day.is_some().then_some(f_year)),
+                day.flatten(),
+                profile,
+                template.as_deref().or(config().template.value.as_deref()),
+            ),
+        },
+        (Command::Wait, true) => bail!("The --all flag cannot be used with wait."),
+        (Command::Submit, false) => submit::submit(f_year, f_day, profile, part, args.first()),
+        (Command::Submit, true) => bail!("The --all flag cannot be used with submit."),
+        (Command::Meta, _) => meta::meta(f_year, day, profile, refresh),
+        (Command::Read, false) => read::read(f_year, f_day, refresh, markdown.as_deref()),
+        (Command::Read, true) => bail!("The --all flag cannot be used with read."),
+        (Command::Config, _) => config::show(),
+        (Command::Doctor, _) => doctor::doctor(fix),
     }
 }
 
@@ -131,28 +341,22 @@ fn print(header: &str, message: impl Display) {
     }
 }
 
-fn download(url: &str) -> Result<String> {
-    let url: Url = url.parse()?;
-
-    let cookie = format!(
-        "session={}",
-        env::var("AOC_SESSION_ID").context("`AOC_SESSION_ID` must be set")?
-    );
-    let jar = Jar::default();
-    jar.add_cookie_str(&cookie, &url);
+/// The Advent of Code server, overridable with `AOC_BASE_URL`.
+fn base_url() -> String {
+    config().base_url.value.clone()
+}
 
-    Ok(reqwest::blocking::ClientBuilder::new()
-        .cookie_provider(Arc::new(jar))
-        .user_agent("https://github.com/Dragon-Hatcher/AdventOfCode danieldragonhatcher@gmail.com")
-        .build()?
-        .get(url)
-        .send()?
-        .text()?)
+fn warn(header: &str, message: impl Display) {
+    if io::stdout().is_terminal() {
+        println!("{:>12} {}", Paint::yellow(&header).bold(), message);
+    } else {
+        println!("{:>12} {}", header, message);
+    }
 }
 
-fn ensure_input_fetched(year: u32, day: u32) -> Result<()> {
-    let workspace_path = PathBuf::from(env!("CARGO_WORKSPACE_DIR"));
-    let input_path = workspace_path.join(format!("input/{year:04}/{day:02}.txt"));
+fn ensure_input_fetched(year: u32, day: u32, profile: &str) -> Result<()> {
+    let workspace_path = get_workspace_path();
+    let input_path = workspace_path.join(profile::input_path(year, day, profile));
 
     fs::create_dir_all(input_path.parent().unwrap())?;
 
@@ -162,18 +366,17 @@ fn ensure_input_fetched(year: u32, day: u32) -> Result<()> {
         .display();
 
     if !input_path.exists() {
+        let of = match profile {
+            profile::DEFAULT => String::new(),
+            profile => format!(", profile: {profile}"),
+        };
         print(
             "Downloading",
-            format!("puzzle input (year: {year:04}, day: {day:02})"),
+            format!("puzzle input (year: {year:04}, day: {day:02}{of})"),
         );
-        let url = format!("https://adventofcode.com/{year}/day/{day}/input");
-        let text = download(&url)?;
-
-        if text != include_str!("error.txt") {
-            fs::write(&input_path, text)?;
-        } else {
-            bail!("Tried to fetch input for future day.");
-        }
+        let url = format!("{}/{year}/day/{day}/input", base_url());
+        let text = http::download(&url, profile)?;
+        fs::write(&input_path, text)?;
     }
 
     print("Verified", format!("puzzle input `{input_display}`"));
@@ -181,37 +384,123 @@ fn ensure_input_fetched(year: u32, day: u32) -> Result<()> {
     Ok(())
 }
 
-fn run(year: u32, day: u32, args: &[String]) -> Result<()> {
-    ensure_input_fetched(year, day)?;
+/// Downloads the default profile's input when running as another one, since
+/// `include_input!` embeds it and the day doesn't build without it.
+fn ensure_default_input(year: u32, day: u32, profile: &str) -> Result<()> {
+    if profile == profile::DEFAULT {
+        return Ok(());
+    }
+    ensure_input_fetched(year, day, profile::DEFAULT).with_context(|| {
+        format!(
+            "The default input `{}` is missing and every profile needs it to build.",
+            profile::input_path(year, day, profile::DEFAULT)
+        )
+    })
+}
+
+/// Splits arguments at the first `--` into those for cargo and those for the
+/// binary.
+fn split_args(args: &[String]) -> (&[String], &[String]) {
+    match args.iter().position(|a| a == "--") {
+        Some(i) => (&args[..i], &args[i + 1..]),
+        None => (args, &[][..]),
+    }
+}
+
+/// Makes `include_input!` read the input of `profile` instead of the one it
+/// embedded.
+fn set_input(cmd: &mut process::Command, year: u32, day: u32, profile: &str) {
+    if profile != profile::DEFAULT {
+        let input_path = profile::input_path(year, day, profile);
+        cmd.env("ADVENT_INPUT", get_workspace_path().join(input_path));
+    }
+}
+
+fn repeat_args(repeat: Option<NonZeroUsize>) -> Vec<String> {
+    match repeat {
+        Some(n) => vec!["--repeat".to_owned(), n.to_string()],
+        None => Vec::new(),
+    }
+}
+
+fn run(
+    year: u32,
+    day: u32,
+    profile: &str,
+    repeat: Option<NonZeroUsize>,
+    args: &[String],
+) -> Result<()> {
+    ensure_input_fetched(year, day, profile)?;
+    ensure_default_input(year, day, profile)?;
 
     let bin_name = format!("{year:04}{day:02}");
 
-    let status = process::Command::new(env!("CARGO"))
-        .args(["run", "--release", "--bin", &bin_name])
-        .args(args)
-        .status()?;
+    // we only need the answers back if there are some we know are wrong
+    let rejected = load_metadata()?
+        .day(profile, &bin_name)
+        .filter(|m| !m.wrong.is_empty())
+        .cloned();
+    let json_path = get_workspace_path().join(format!("target/advent/{bin_name}.json"));
+
+    // always built with the same features as run --all and submit, so
+    // switching between them doesn't rebuild the day
+    let (cargo_args, bin_args) = split_args(args);
+    let mut cmd = process::Command::new(env!("CARGO"));
+    cmd.args(["run", "--release", "--features", "json", "--bin", &bin_name])
+        .args(cargo_args)
+        .arg("--")
+        .args(repeat_args(repeat));
+    if rejected.is_some() {
+        fs::create_dir_all(json_path.parent().unwrap())?;
+        cmd.arg("--json-file").arg(&json_path);
+    }
+    set_input(&mut cmd, year, day, profile);
+    let status = cmd.args(bin_args).status()?;
+
+    if let Some(meta) = rejected {
+        if let Ok(text) = fs::read_to_string(&json_path) {
+            warn_rejected(&meta, serde_json::from_str(&text)?);
+        }
+    }
 
     process::exit(status.code().unwrap_or(1))
 }
 
-fn get_day_meta(year: u32, day: u32) -> Result<DayMeta> {
+/// Warns about any answers that are already known to be wrong.
+fn warn_rejected(meta: &DayMeta, summary: Summary) {
+    let runs: Vec<RunSummary> = match summary {
+        Summary::Run(runs) => runs,
+        Summary::Repeat(repeats) => repeats.into_iter().map(Into::into).collect(),
+        Summary::Bench { .. } => return,
+    };
+
+    for run in runs {
+        let Some(part) = run.name.strip_prefix("Part ").and_then(|n| n.parse().ok()) else {
+            continue;
+        };
+        let Some(answer) = run.result.as_ref().and_then(Answer::submission) else {
+            continue;
+        };
+        if let Some(rejection) = meta.rejection(part, &answer) {
+            warn(
+                "Warning",
+                format!("{} answer `{answer}` {rejection}", run.name),
+            );
+        }
+    }
+}
+
+fn get_day_meta(year: u32, day: u32, profile: &str) -> Result<DayMeta> {
     if !has_occurred(year, day) {
-        return Ok(DayMeta {
-            name: None,
-            answer1: None,
-            answer2: None,
-        });
+        return Ok(DayMeta::for_day(year, day));
     }
 
-    let url = format!("https://adventofcode.com/{year}/day/{day}");
-    let text = download(&url)?;
+    let url = format!("{}/{year}/day/{day}", base_url());
+    let text = http::download_page(&url, profile)?;
 
-    let title_re = regex!(r#"<h2>--- Day \d+: (.?*)---</h2>"#);
     let answer_re = regex!(r#"Your puzzle answer was <code>(.*?)</code>"#);
 
-    let name = title_re
-        .captures(&text)
-        .map(|m| m[1].trim().to_owned().replace("&apos;", "'"));
+    let name = puzzle::title(&text);
     let mut answers = answer_re.captures_iter(&text);
     let answer1 = answers.next().map(|m| m[1].trim().to_owned());
     let answer2 = answers.next().map(|m| m[1].trim().to_owned());
@@ -220,11 +509,26 @@ fn get_day_meta(year: u32, day: u32) -> Result<DayMeta> {
         name,
         answer1,
         answer2,
+        ..DayMeta::for_day(year, day)
     })
 }
 
+/// Fetches the metadata for a day as seen by `profile`, keeping the answers
+/// we know are wrong.
+fn refresh_day_meta(metadata: &mut AllMetadata, year: u32, day: u32, profile: &str) -> Result<()> {
+    let bin_name = format!("{year:04}{day:02}");
+    let mut meta = get_day_meta(year, day, profile)?;
+    let days = metadata.days_mut(profile);
+    if let Some(old) = days.remove(&bin_name) {
+        meta.wrong = old.wrong;
+        meta.started = old.started;
+    }
+    days.insert(bin_name, meta);
+    write_metadata(metadata)
+}
+
 fn load_metadata() -> Result<AllMetadata> {
-    let workspace_path = PathBuf::from(env!("CARGO_WORKSPACE_DIR"));
+    let workspace_path = get_workspace_path();
     let meta_path = workspace_path.join(format!("input/metadata.json"));
 
     fs::create_dir_all(meta_path.parent().unwrap())?;
@@ -232,35 +536,150 @@ fn load_metadata() -> Result<AllMetadata> {
     let meta = if !meta_path.exists() {
         let m = AllMetadata {
             days: Default::default(),
+            profiles: Default::default(),
         };
         write_metadata(&m)?;
         m
     } else {
         let text = fs::read_to_string(meta_path)?;
-        serde_json::from_str(&text)?
+        let mut meta: AllMetadata = serde_json::from_str(&text)?;
+        meta.fix_parts();
+        meta
     };
 
     Ok(meta)
 }
 
 fn write_metadata(meta: &AllMetadata) -> Result<()> {
-    let workspace_path = PathBuf::from(env!("CARGO_WORKSPACE_DIR"));
+    let workspace_path = get_workspace_path();
     let meta_path = workspace_path.join(format!("input/metadata.json"));
     fs::write(meta_path, serde_json::to_string(meta)?)?;
 
     Ok(())
 }
 
-fn run_all(year: Option<u32>, args: &[String]) -> Result<()> {
+/// Runs each executable on a pool of `jobs` threads, sending the output of
+/// the `i`th back as `(i, output)`. Executables with an input path are run
+/// against that input. Bins that didn't build are sent as `None`.
+fn run_executables(
+    executables: Vec<Option<(PathBuf, Option<PathBuf>)>>,
+    args: &[String],
+    jobs: usize,
+) -> mpsc::Receiver<(usize, Option<Output>)> {
+    let (tx, rx) = mpsc::channel();
+    let queue: Arc<Mutex<_>> = Arc::new(Mutex::new(executables.into_iter().enumerate()));
+    let args = args.to_vec();
+
+    for _ in 0..jobs.max(1) {
+        let queue = queue.clone();
+        let tx = tx.clone();
+        let args = args.clone();
+        thread::spawn(move || loop {
+            let Some((i, executable)) = queue.lock().unwrap().next() else {
+                break;
+            };
+            let output = executable.and_then(|(executable, input)| {
+                let mut cmd = process::Command::new(executable);
+                cmd.args(&args).current_dir(get_workspace_path());
+                if let Some(input) = input {
+                    cmd.env("ADVENT_INPUT", input);
+                }
+                cmd.output().ok()
+            });
+            if tx.send((i, output)).is_err() {
+                break;
+            }
+        });
+    }
+
+    rx
+}
+
+/// Whether the answer to part `n` is known to be right or wrong, and why
+/// it is wrong if an earlier submission rules it out.
+fn answer_status(p: &RunSummary, day_meta: &DayMeta, n: u8) -> (Status, Option<Rejection>) {
+    let expected = match n {
+        1 => &day_meta.answer1,
+        _ => &day_meta.answer2,
+    };
+    let correct = match (&p.result, expected) {
+        (Some(answer), Some(e)) => answer.check(e),
+        (None, Some(_)) => Some(false),
+        (_, None) => None,
+    };
+    let rejection = p
+        .result
+        .as_ref()
+        .and_then(Answer::submission)
+        .and_then(|a| day_meta.rejection(n, &a));
+    let status = match (correct, rejection) {
+        (Some(true), _) => Status::Right,
+        (Some(false), _) | (_, Some(_)) => Status::Wrong,
+        (None, None) => Status::Unknown,
+    };
+    (status, rejection)
+}
+
+fn run_all(
+    selection: &Selection,
+    profile: &str,
+    repeat: Option<NonZeroUsize>,
+    jobs: Option<usize>,
+    offline: bool,
+    args: &[String],
+) -> Result<()> {
     let binaries = get_binaries()?;
-    let year_str = year.map(|y| format!("{y:04}")).unwrap_or_default();
-    let binaries = binaries
-        .bin
-        .iter()
-        .map(|b| &b.name)
-        .filter(|n| n.starts_with(&year_str));
+    let binaries = selection.select(&binaries)?;
+
+    let executables = build_bins(
+        &["build", "--release", "--features", "json", "--keep-going"],
+        &binaries,
+    )?;
+
+    // every profile runs the same build, only the input changes
+    let profiles = match profile {
+        profile::ALL => profile::names(),
+        profile => vec![profile.to_owned()],
+    };
+    let mut executions = Vec::new();
+    for bin in binaries {
+        let year: u32 = bin[..4].parse()?;
+        let day: u32 = bin[4..].parse()?;
+        for profile in &profiles {
+            if profile == profile::DEFAULT {
+                executions.push((bin, profile, None));
+                continue;
+            }
+            let input_path = get_workspace_path().join(profile::input_path(year, day, profile));
+            if !input_path.exists() {
+                let fetched = match offline {
+                    true => Err(anyhow!("the input is not downloaded")),
+                    false => ensure_input_fetched(year, day, profile),
+                };
+                if let Err(e) = fetched {
+                    warn("Skipped", format!("{bin} for profile {profile}: {e}"));
+                    continue;
+                }
+            }
+            executions.push((bin, profile, Some(input_path)));
+        }
+    }
+
+    let mut bin_args = vec!["--output".to_owned(), "json".to_owned()];
+    bin_args.extend(repeat_args(repeat));
+    bin_args.extend_from_slice(args);
+    let results = run_executables(
+        executions
+            .iter()
+            .map(|(bin, _, input)| Some((executables.get(*bin)?.clone(), input.clone())))
+            .collect(),
+        &bin_args,
+        jobs.unwrap_or(1),
+    );
+    let mut pending = HashMap::new();
 
     let mut metadata = load_metadata()?;
+    let mut last_runs = load_last_runs();
 
     let mut success = true;
 
@@ -290,7 +709,7 @@ fn run_all(year: Option<u32>, args: &[String]) -> Result<()> {
 
     let mut prev_year = 0;
 
-    for bin in binaries {
+    for (i, &(bin, profile, _)) in executions.iter().enumerate() {
         let year: u32 = bin.chars().take(4).collect::<String>().parse()?;
         let day: u32 = bin.chars().skip(4).collect::<String>().parse()?;
 
@@ -300,45 +719,77 @@ fn run_all(year: Option<u32>, args: &[String]) -> Result<()> {
                 pc = "",
                 n_width = NAME_WIDTH,
                 width = PART_WIDTH
-            );                    
+            );
         }
         prev_year = year;
 
-        let Output { status, stdout, .. } = process::Command::new(env!("CARGO"))
-            .args([
-                "run",
-                "--quiet",
-                "--features",
-                "json",
-                "--release",
-                "--bin",
-                &bin,
-                "--",
-                "--output",
-                "json",
-            ])
-            .args(args)
-            .output()?;
-
-        let stdout = String::from_utf8_lossy(&stdout);
-        let Summary::Run(runs) = serde_json::from_str(&stdout)? else { panic!("Got benchmark data?")};
-
-        let part1 = runs.iter().find(|p| p.name == "Part 1");
-        let part2 = runs.iter().find(|p| p.name == "Part 2");
+        // days finish out of order so hold on to them until it is their turn
+        let output = loop {
+            if let Some(output) = pending.remove(&i) {
+                break output;
+            }
+            let (j, output) = results.recv()?;
+            pending.insert(j, output);
+        };
+        let built = output.is_some();
+        let runs: Vec<RunSummary> = match output {
+            Some(Output { status, stdout, .. }) => {
+                success = success && status.success();
+                let stdout = String::from_utf8_lossy(&stdout);
+                match serde_json::from_str(&stdout)? {
+                    Summary::Run(runs) => runs,
+                    Summary::Repeat(repeats) => repeats.into_iter().map(Into::into).collect(),
+                    Summary::Bench { .. } => panic!("Got benchmark data?"),
+                }
+            }
+            None => {
+                success = false;
+                Vec::new()
+            }
+        };
 
-        if !metadata.days.contains_key(bin) || !metadata.days.get(bin).unwrap().has_all()  {
-            let m = get_day_meta(year, day)?;
-            metadata.days.insert(bin.clone(), m);
-            write_metadata(&metadata)?;
+        if !offline && !metadata.day(profile, bin).is_some_and(DayMeta::has_all) {
+            refresh_day_meta(&mut metadata, year, day, profile)?;
         }
 
-        let day_meta = metadata.days.get(bin).unwrap();
+        let unknown = DayMeta::for_day(year, day);
+        let day_meta = metadata.day(profile, bin).unwrap_or(&unknown);
+
+        // a second part on a day that has one is left out
+        let part1 = runs.iter().find(|p| p.name == "Part 1");
+        let part2 = runs
+            .iter()
+            .find(|p| p.name == "Part 2")
+            .filter(|_| day_meta.parts > 1);
+        let part1 = part1.map(|p| (p, answer_status(p, day_meta, 1)));
+        let part2 = part2.map(|p| (p, answer_status(p, day_meta, 2)));
+
+        if built {
+            let parts: Vec<_> = [part1, part2]
+                .into_iter()
+                .flatten()
+                .map(|(p, (status, _))| (status, p.time))
+                .collect();
+            // a day is wrong or slow if it is for any of the profiles
+            match i > 0 && executions[i - 1].0 == bin {
+                true => last_runs
+                    .entry(bin.clone())
+                    .or_default()
+                    .parts
+                    .extend(parts),
+                false => _ = last_runs.insert(bin.clone(), LastRun { parts }),
+            }
+        } else {
+            last_runs.remove(bin);
+        }
 
-        fn part(p: Option<&RunSummary>, expected: &Option<String>) -> impl Display {
+        fn part(p: Option<(&RunSummary, (Status, Option<Rejection>))>) -> impl Display {
             match p {
-                Some(p) => {
-                    let result = &p.result;
-                    let result = result.replace("\n", "↩");
+                Some((p, (status, rejection))) => {
+                    let result = match &p.result {
+                        Some(answer) => answer.summary(),
+                        None => "🚨👻🚨".to_owned(),
+                    };
                     let trimmed_result = if result.len() <= PART_WIDTH - 11 {
                         result.clone()
                     } else {
@@ -349,16 +800,17 @@ fn run_all(year: Option<u32>, args: &[String]) -> Result<()> {
                     let time = format!("{}", Time::new(p.time.as_secs_f64()));
                     let width = PART_WIDTH.saturating_sub(result.chars().count() + 1);
 
-                    let correct = expected.as_ref().map(|e| e == &p.result);
-                    let correct_char = match correct {
-                        Some(true) => Paint::green('✓'),
-                        Some(false) => Paint::red('✗'),
-                        None => Paint::fixed(245, '?'),
+                    let correct_char = match (status, rejection) {
+                        (Status::Right, _) => Paint::green('✓'),
+                        (_, Some(Rejection::NotBelow(_))) => Paint::yellow('↑'),
+                        (_, Some(Rejection::NotAbove(_))) => Paint::yellow('↓'),
+                        (Status::Wrong, _) => Paint::red('✗'),
+                        (Status::Unknown, _) => Paint::fixed(245, '?'),
                     };
 
                     format!(
                         "{} {:>width$} │ {}",
-                        if correct == Some(false) {
+                        if status == Status::Wrong {
                             Paint::red(trimmed_result).bold()
                         } else {
                             Paint::new(trimmed_result).bold()
@@ -376,13 +828,28 @@ fn run_all(year: Option<u32>, args: &[String]) -> Result<()> {
             }
         }
 
+        // the last day's second star is given for finishing the others, so
+        // there is nothing to run or check
+        fn final_part() -> String {
+            format!(
+                "{:^width$}",
+                Paint::fixed(245, "no second part"),
+                width = PART_WIDTH + 4
+            )
+        }
+
+        // titles are the same for everyone
         let mut puzzle_name = day_meta
             .name
             .as_ref()
+            .or_else(|| metadata.day(profile::DEFAULT, bin)?.name.as_ref())
             .map(|s| s.as_str())
             .unwrap_or_default()
             .to_owned();
-        if puzzle_name.len() > NAME_WIDTH { 
+        if profiles.len() > 1 {
+            puzzle_name = format!("[{profile}] {puzzle_name}");
+        }
+        if puzzle_name.len() > NAME_WIDTH {
             puzzle_name = puzzle_name.chars().take(NAME_WIDTH - 1).collect();
             puzzle_name.push('…');
         };
@@ -391,13 +858,20 @@ fn run_all(year: Option<u32>, args: &[String]) -> Result<()> {
             "│ {}: {:<n_width$} │ {:<width$} │ {:<width$} │",
             Paint::cyan(format!("{year:04} / {day:02}")).bold(),
             puzzle_name,
-            part(part1, &day_meta.answer1),
-            part(part2, &day_meta.answer2),
+            if built {
+                part(part1).to_string()
+            } else {
+                // padded before painting so the escape codes aren't counted
+                let failed = format!("{:<width$}", "failed to build", width = PART_WIDTH + 4);
+                Paint::red(failed).bold().to_string()
+            },
+            match day_meta.parts {
+                1 => final_part(),
+                _ => part(part2).to_string(),
+            },
             n_width = NAME_WIDTH,
             width = PART_WIDTH + 4
         );
-
-        success = success && status.success();
     }
 
     println!(
@@ -407,6 +881,8 @@ fn run_all(year: Option<u32>, args: &[String]) -> Result<()> {
         width = PART_WIDTH
     );
 
+    write_last_runs(&last_runs)?;
+
     if !success {
         process::exit(1);
     }
@@ -414,39 +890,193 @@ fn run_all(year: Option<u32>, args: &[String]) -> Result<()> {
     Ok(())
 }
 
-fn test(year: u32, day: u32, args: &[String]) -> Result<()> {
-    ensure_input_fetched(year, day)?;
+/// Starts a fresh `input/YYYY/DD.answers` snapshot containing only the
+/// answers already accepted by Advent of Code.
+fn seed_answers(year: u32, day: u32, answers_path: &Path) -> Result<()> {
+    let metadata = load_metadata()?;
+    let known = match metadata.days.get(&format!("{year:04}{day:02}")) {
+        Some(meta) => vec![meta.answer1.clone(), meta.answer2.clone()],
+        None => Vec::new(),
+    };
+
+    let text: String = known
+        .into_iter()
+        .enumerate()
+        .filter_map(|(i, a)| Some(format!("--- Part {} ---\n{}\n", i + 1, a?)))
+        .collect();
+    fs::write(answers_path, text)?;
+
+    Ok(())
+}
+
+fn test(year: u32, day: u32, bless: bool, args: &[String]) -> Result<()> {
+    ensure_input_fetched(year, day, profile::DEFAULT)?;
 
     let bin_name = format!("{year:04}{day:02}");
 
-    let status = process::Command::new(env!("CARGO"))
-        .args(["test", "--release", "--bin", &bin_name])
-        .args(args)
-        .status()?;
+    let mut cmd = process::Command::new(env!("CARGO"));
+    cmd.args(["test", "--release", "--bin", &bin_name]);
+    if !bless {
+        let status = cmd.args(args).status()?;
+        process::exit(status.code().unwrap_or(1))
+    }
+
+    let answers = format!("input/{year:04}/{day:02}.answers");
+    let answers_path = get_workspace_path().join(&answers);
+    let previous = fs::read_to_string(&answers_path).ok();
+    seed_answers(year, day, &answers_path)?;
+
+    let (cargo_args, bin_args) = split_args(args);
+    let mut child = cmd
+        .args(cargo_args)
+        .args(["--", "--exact", "answers"])
+        .args(bin_args)
+        .env("ADVENT_BLESS", "1")
+        .stdout(Stdio::piped())
+        .spawn()?;
+    // the snapshot is only kept if the `answers!` test actually ran
+    let mut ran = false;
+    for line in BufReader::new(child.stdout.take().unwrap()).lines() {
+        let line = line?;
+        ran |= line == "test answers ... ok";
+        println!("{line}");
+    }
+    let status = child.wait()?;
+
+    let blessed = fs::read_to_string(&answers_path).unwrap_or_default();
+    if ran && status.success() && !blessed.is_empty() {
+        print("Blessed", answers);
+    } else {
+        match previous {
+            Some(previous) => fs::write(&answers_path, previous)?,
+            None => fs::remove_file(&answers_path)?,
+        }
+        if status.success() {
+            warn(
+                "Warning",
+                format!(
+                    "nothing was blessed, add `answers!` to {}",
+                    display_bin_path(year, day)
+                ),
+            );
+        }
+    }
 
     process::exit(status.code().unwrap_or(1))
 }
 
-fn bench(year: u32, day: u32, args: &[String]) -> Result<()> {
-    ensure_input_fetched(year, day)?;
+fn bench(year: u32, day: u32, profile: &str, args: &[String]) -> Result<()> {
+    ensure_input_fetched(year, day, profile)?;
+    ensure_default_input(year, day, profile)?;
 
     let bin_name = format!("{year:04}{day:02}");
 
-    let (cargo_args, bin_args) = match args.iter().position(|a| a == "--") {
-        Some(i) => (&args[..i], &args[i + 1..]),
-        None => (args, &[][..]),
-    };
-    let status = process::Command::new(env!("CARGO"))
-        .args(["run", "--release", "--bin", &bin_name])
+    let (cargo_args, bin_args) = split_args(args);
+    let mut cmd = process::Command::new(env!("CARGO"));
+    cmd.args(["run", "--release", "--bin", &bin_name])
         .args(cargo_args)
         .args(["--", "--bench"])
-        .args(bin_args)
-        .status()?;
+        .args(bin_args);
+    set_input(&mut cmd, year, day, profile);
+    let status = cmd.status()?;
 
     process::exit(status.code().unwrap_or(1))
 }
 
-fn new(year: u32, day: u32) -> Result<()> {
+/// Saves the examples as `input/YYYY/DD.exampleN.txt`.
+fn save_examples(year: u32, day: u32, examples: &[Example]) -> Result<()> {
+    let workspace_path = get_workspace_path();
+    for (i, example) in examples.iter().enumerate() {
+        let path = format!("input/{year:04}/{day:02}.example{}.txt", i + 1);
+        let example_path = workspace_path.join(&path);
+        fs::create_dir_all(example_path.parent().unwrap())?;
+        fs::write(example_path, &example.input)?;
+        print("Saved", format!("example `{path}`"));
+    }
+
+    Ok(())
+}
+
+/// Renders a test for each example, or an empty one to fill in by hand.
+fn example_tests(year: u32, day: u32, examples: &[Example]) -> String {
+    if examples.is_empty() {
+        return r#"#[test]
+fn example() {
+    let input = "";
+    assert_eq!(part1(input), 0);
+    assert_eq!(part2(input), 0);
+}"#
+        .to_owned();
+    }
+
+    let mut tests = String::new();
+    for (i, example) in examples.iter().enumerate() {
+        let name = match examples.len() {
+            1 => "example".to_owned(),
+            _ => format!("example{}", i + 1),
+        };
+        let path = format!("../input/{year:04}/{day:02}.example{}.txt", i + 1);
+        tests += &format!("#[test]\nfn {name}() {{\n    let input = include_str!({path:?});\n");
+        for (part, answer) in example.answers.iter().enumerate() {
+            let Some(answer) = answer else { continue };
+            let part = part + 1;
+            // the templates' parts return `i64`, so other answers would not
+            // build until the part's return type is changed
+            tests += &match answer.parse::<i64>() {
+                Ok(n) => format!("    assert_eq!(part{part}(input), {n});\n"),
+                Err(_) => format!("    // assert_eq!(part{part}(input), {answer:?});\n"),
+            };
+        }
+        tests += "}\n\n";
+    }
+    tests.trim_end().to_owned()
+}
+
+/// Fills in a template's variables for a day.
+fn render_template(
+    source: &str,
+    year: u32,
+    day: u32,
+    title: Option<&str>,
+    shape: Shape,
+    examples: &[Example],
+) -> String {
+    let example = examples
+        .first()
+        .map_or_else(|| "\"\"".to_owned(), |e| scaffold::raw_string(&e.input));
+    source
+        .replace("{ year }", &format!("{year:04}"))
+        .replace("{ day }", &format!("{day:02}"))
+        .replace("{ title }", title.unwrap_or_default())
+        .replace("{ shape }", &shape.to_string())
+        .replace("{ examples }", &example_tests(year, day, examples))
+        .replace("{ example }", &example)
+}
+
+/// Fetches what the templates need to know about a released puzzle: its
+/// title, examples and the shape of the input.
+fn fetch_puzzle(
+    year: u32,
+    day: u32,
+    profile: &str,
+) -> Result<(Option<String>, Vec<Example>, Shape)> {
+    let html = http::download_page(&format!("{}/{year}/day/{day}", base_url()), profile)?;
+    let examples = puzzle::examples(&html);
+    save_examples(year, day, &examples)?;
+
+    ensure_input_fetched(year, day, profile)?;
+    let input_path = get_workspace_path().join(profile::input_path(year, day, profile));
+    let shape = Shape::detect(&fs::read_to_string(input_path)?);
+
+    Ok((puzzle::title(&html), examples, shape))
+}
+
+fn new(year: u32, day: u32, profile: &str, template: Option<&str>) -> Result<()> {
+    let days = calendar(year).days;
+    if !calendar(year).has_day(day) {
+        bail!("{year} has {days} days, there is no day {day}.");
+    }
+
     let bin_name = get_bin_name(year, day);
     let bin_path = get_bin_path(year, day);
     let bin_display = display_bin_path(year, day);
@@ -454,26 +1084,43 @@ fn new(year: u32, day: u32) -> Result<()> {
     if bin_path.exists() {
         print("Verified", format!("{bin_display} already exists"));
     } else {
-        const TEMPLATE: &str = include_str!("template.rs");
+        let source = scaffold::load_template(template)?;
+
+        let (title, examples, shape) = if has_occurred(year, day) {
+            fetch_puzzle(year, day, profile).unwrap_or_else(|e| {
+                warn("Warning", format!("could not fetch the puzzle: {e}"));
+                (None, Vec::new(), Shape::Empty)
+            })
+        } else {
+            (None, Vec::new(), Shape::Empty)
+        };
+
+        if let (None, Some(suggested)) = (template, shape.template()) {
+            if scaffold::templates().iter().any(|t| t == suggested) {
+                print(
+                    "Note",
+                    format!("the input is {shape}, try `--template {suggested}`"),
+                );
+            }
+        }
 
-        let rendered = TEMPLATE
-            .replace("{ year }", &format!("{year:04}"))
-            .replace("{ day }", &format!("{day:02}"));
+        let rendered = render_template(&source, year, day, title.as_deref(), shape, &examples);
         fs::create_dir_all(bin_path.parent().unwrap())?;
         fs::write(&bin_path, rendered)?;
         print("Created", bin_display);
     }
 
-    let mut bins = get_binaries()?;
-    let added = bins.ensure_has(year, day);
-    write_binaries(bins)?;
-
-    if added {
-        print("Added", format!("{bin_name} binary to Cargo manifest"));
-    } else {
+    let had_shim = get_shim_path(&bin_name).exists();
+    sync_bins()?;
+    if had_shim {
         print(
             "Verified",
-            format!("{bin_name} binary already exists in Cargo manifest"),
+            format!("{bin_name} binary already exists as `src/bin/{bin_name}.rs`"),
+        );
+    } else {
+        print(
+            "Added",
+            format!("{bin_name} binary as `src/bin/{bin_name}.rs`"),
         );
     }
 
@@ -482,7 +1129,71 @@ fn new(year: u32, day: u32) -> Result<()> {
         format!("Use `cargo advent -y {year} -d {day} run` to run"),
     );
 
-    _ = process::Command::new("code").arg(bin_path).status();
+    let mut editor = config().editor.value.split_whitespace();
+    if let Some(program) = editor.next() {
+        _ = process::Command::new(program)
+            .args(editor)
+            .arg(bin_path)
+            .status();
+    }
+
+    Ok(())
+}
+
+/// Removes a day's solution and with it its binary.
+fn remove(year: u32, day: u32) -> Result<()> {
+    let bin_name = get_bin_name(year, day);
+    let bin_path = get_bin_path(year, day);
+    if !bin_path.exists() {
+        bail!("There is no {bin_name} binary.");
+    }
+    fs::remove_file(&bin_path)?;
+    sync_bins()?;
+
+    print(
+        "Removed",
+        format!("{} and the {bin_name} binary", display_bin_path(year, day)),
+    );
+    Ok(())
+}
+
+/// Moves a day's solution to another day, pointing it at that day's input.
+fn rename(year: u32, day: u32, to: &str) -> Result<()> {
+    let Some((to_year, to_day)) = parse_bin_name(to) else {
+        bail!("`{to}` is not a bin name like 202318.");
+    };
+    let bin_name = get_bin_name(year, day);
 
+    let from_path = get_bin_path(year, day);
+    if !from_path.exists() {
+        bail!("There is no {bin_name} binary.");
+    }
+    let to_path = get_bin_path(to_year, to_day);
+    if to_path.exists() {
+        bail!("There is already a {to} binary.");
+    }
+
+    let source = fs::read_to_string(&from_path)?
+        .replace(
+            &format!("{year:04} / {day:02}"),
+            &format!("{to_year:04} / {to_day:02}"),
+        )
+        .replace(
+            &format!("input/{year:04}/{day:02}."),
+            &format!("input/{to_year:04}/{to_day:02}."),
+        );
+    fs::create_dir_all(to_path.parent().unwrap())?;
+    fs::write(&to_path, source)?;
+    fs::remove_file(&from_path)?;
+    sync_bins()?;
+
+    print(
+        "Renamed",
+        format!(
+            "{bin_name} to {to}, `{}` is now `{}`",
+            display_bin_path(year, day),
+            display_bin_path(to_year, to_day)
+        ),
+    );
     Ok(())
 }
@@ -0,0 +1,67 @@
+//! Inspect and sync the cached puzzle metadata.
+
+use anyhow::Result;
+
+use crate::{
+    calendar::calendar, has_occurred, json::DayMeta, load_metadata, print, profile,
+    refresh_day_meta, select::Days, warn,
+};
+
+fn describe(meta: &DayMeta) -> String {
+    let name = meta.name.as_deref().unwrap_or("?");
+    let answers = [&meta.answer1, &meta.answer2]
+        .iter()
+        .take(meta.parts.into())
+        .filter(|a| a.is_some())
+        .count();
+    match meta.wrong.len() {
+        0 => format!("{name} ({answers}/{} answers)", meta.parts),
+        wrong => format!("{name} ({answers}/{} answers, {wrong} wrong)", meta.parts),
+    }
+}
+
+/// The released days of `year` in `days`, which may be open-ended like `20..`.
+pub fn released(year: u32, days: Option<Days>) -> impl Iterator<Item = u32> {
+    let last = calendar(year).days;
+    let days = days.unwrap_or(Days {
+        start: 1,
+        end: last,
+    });
+    (days.start..=days.end.min(last)).filter(move |&day| has_occurred(year, day))
+}
+
+/// Lists the cached metadata for the released days of `year` as seen by
+/// `profile`, first fetching it again from Advent of Code if `refresh` is set.
+pub fn meta(year: u32, days: Option<Days>, profile: &str, refresh: bool) -> Result<()> {
+    let days = released(year, days);
+
+    let mut metadata = load_metadata()?;
+
+    for day in days {
+        let bin_name = format!("{year:04}{day:02}");
+        if refresh {
+            refresh_day_meta(&mut metadata, year, day, profile)?;
+        }
+
+        match metadata.day(profile, &bin_name) {
+            Some(meta) => print(
+                if refresh { "Synced" } else { "Cached" },
+                format!("{year:04} / {day:02}: {}", describe(meta)),
+            ),
+            None => {
+                let flag = match profile {
+                    profile::DEFAULT => String::new(),
+                    profile => format!(" --profile {profile}"),
+                };
+                warn(
+                    "Missing",
+                    format!(
+                        "{year:04} / {day:02}, sync it with `cargo advent meta --refresh{flag}`"
+                    ),
+                )
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,55 @@
+//! Named Advent of Code accounts, each with its own inputs and answers.
+
+use anyhow::{bail, Context, Result};
+
+use crate::config::{config, session_var};
+
+/// The profile that uses `AOC_SESSION_ID` and whose inputs are the ones
+/// `include_input!` embeds. They are embedded at compile time, so a day
+/// doesn't build for any profile until its default input is downloaded.
+pub const DEFAULT: &str = "default";
+
+/// Selects every profile (run --all only).
+pub const ALL: &str = "all";
+
+/// The names of every profile, starting with the default one.
+pub fn names() -> Vec<String> {
+    let mut names = vec![DEFAULT.to_owned()];
+    names.extend(config().profiles.keys().cloned());
+    names
+}
+
+/// Checks that `profile` is the default one or configured in `advent.toml`.
+pub fn check(profile: &str) -> Result<()> {
+    if profile != DEFAULT && !config().profiles.contains_key(profile) {
+        bail!(
+            "There is no profile named `{profile}`, expected one of: {}.",
+            names().join(", ")
+        );
+    }
+    Ok(())
+}
+
+/// The session token to download and submit with as `profile`.
+pub fn session(profile: &str) -> Result<String> {
+    if profile == DEFAULT {
+        return std::env::var("AOC_SESSION_ID").context("`AOC_SESSION_ID` must be set");
+    }
+    check(profile)?;
+    match &config().profiles[profile] {
+        Some(session) => Ok(session.value.clone()),
+        None => bail!(
+            "The `{profile}` profile has no session, set `{}` or `session` in advent.toml.",
+            session_var(profile)
+        ),
+    }
+}
+
+/// Where the input of `profile` is kept, relative to the workspace. Other
+/// profiles are read at run time, the default one must exist to build.
+pub fn input_path(year: u32, day: u32, profile: &str) -> String {
+    match profile {
+        DEFAULT => format!("input/{year:04}/{day:02}.txt"),
+        profile => format!("input/{year:04}/{day:02}/{profile}.txt"),
+    }
+}
@@ -0,0 +1,137 @@
+//! Read the title and examples out of a puzzle page.
+
+use regex_macro::regex;
+
+/// An example input from the puzzle description with the answers given for
+/// it, indexed by part.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Example {
+    pub input: String,
+    pub answers: [Option<String>; 2],
+}
+
+/// Replaces the HTML entities used on puzzle pages.
+pub fn unescape(html: &str) -> String {
+    html.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+/// The title of the puzzle, without the day.
+pub fn title(html: &str) -> Option<String> {
+    let title_re = regex!(r#"<h2>--- Day \d+: (.?*)---</h2>"#);
+    title_re
+        .captures(html)
+        .map(|m| m[1].trim().to_owned().replace("&apos;", "'"))
+}
+
+fn strip_tags(html: &str) -> String {
+    regex!(r"<[^>]*>").replace_all(html, "").into_owned()
+}
+
+/// Finds the `<pre><code>` blocks in each part's article along with the last
+/// emphasised `<code>` after each one, which is where the puzzle states the
+/// expected answer. Blocks without an answer are usually illustrations and
+/// are skipped. A part without blocks of its own gives the answer for the
+/// previous part's last example.
+pub fn examples(html: &str) -> Vec<Example> {
+    let article_re = regex!(r#"(?s)<article class="day-desc">(.*?)</article>"#);
+    let pre_re = regex!(r#"(?s)<pre><code>(.*?)</code></pre>"#);
+    let answer_re = regex!(r#"(?s)<code><em>(.*?)</em></code>|<em><code>(.*?)</code></em>"#);
+
+    let last_answer = |text: &str| {
+        answer_re.captures_iter(text).last().map(|c| {
+            let answer = c.get(1).or(c.get(2)).unwrap().as_str();
+            unescape(&strip_tags(answer)).trim().to_owned()
+        })
+    };
+
+    let mut examples: Vec<Example> = Vec::new();
+    let articles = article_re.captures_iter(html).map(|c| c.get(1).unwrap());
+    for (part, article) in articles.take(2).enumerate() {
+        let article = article.as_str();
+        let blocks: Vec<_> = pre_re.captures_iter(article).collect();
+
+        if blocks.is_empty() {
+            if let (Some(example), Some(answer)) = (examples.last_mut(), last_answer(article)) {
+                example.answers[part] = Some(answer);
+            }
+            continue;
+        }
+
+        for (i, block) in blocks.iter().enumerate() {
+            let end = blocks
+                .get(i + 1)
+                .map_or(article.len(), |b| b.get(0).unwrap().start());
+            let Some(answer) = last_answer(&article[block.get(0).unwrap().end()..end]) else {
+                continue;
+            };
+            let input = unescape(&strip_tags(&block[1]));
+
+            match examples.iter_mut().find(|e| e.input == input) {
+                Some(example) => example.answers[part] = Some(answer),
+                None => {
+                    let mut answers = [None, None];
+                    answers[part] = Some(answer);
+                    examples.push(Example { input, answers });
+                }
+            }
+        }
+    }
+    examples
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scrape() {
+        let html = r#"<main>
+<article class="day-desc"><h2>--- Day 1: Trebuchet?! ---</h2>
+<p>For example:</p>
+<pre><code>1abc2
+pqr3stu8vwx
+a1b2c3d4e5f
+treb7uchet
+</code></pre>
+<p>In this example, the calibration values of these four lines are <code>12</code>, <code>38</code>, <code>15</code>, and <code>77</code>. Adding these together produces <code><em>142</em></code>.</p>
+<p>Consider your entire calibration document.</p>
+</article>
+<p>Your puzzle answer was <code>54632</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+<pre><code>two1nine
+<em>eight</em>wothree
+</code></pre>
+<p>Adding these together produces <code><em>281</em></code>.</p>
+</article>
+</main>"#;
+        assert_eq!(
+            examples(html),
+            [
+                Example {
+                    input: "1abc2\npqr3stu8vwx\na1b2c3d4e5f\ntreb7uchet\n".into(),
+                    answers: [Some("142".into()), None],
+                },
+                Example {
+                    input: "two1nine\neightwothree\n".into(),
+                    answers: [None, Some("281".into())],
+                },
+            ]
+        );
+
+        let reused = r#"<article class="day-desc"><pre><code>a -&gt; b</code></pre>
+<p>There are <code><em>3</em></code> paths.</p></article>
+<article class="day-desc"><p>Now there are <em>more</em>, <code><em>7</em></code>.</p></article>"#;
+        assert_eq!(
+            examples(reused),
+            [Example {
+                input: "a -> b".into(),
+                answers: [Some("3".into()), Some("7".into())],
+            }]
+        );
+    }
+}
@@ -0,0 +1,336 @@
+//! Render the puzzle description in the terminal or as Markdown.
+
+use std::{env, fs, path::Path};
+
+use anyhow::{bail, Result};
+use regex_macro::regex;
+use yansi::Paint;
+
+use crate::{
+    base_url, cargo::get_workspace_path, has_occurred, http, print, profile, puzzle::unescape,
+};
+
+/// How a piece of text is emphasised.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct Style {
+    em: bool,
+    code: bool,
+}
+
+/// Text and whether there was whitespace before it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Piece {
+    space: bool,
+    text: String,
+    style: Style,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Block {
+    Heading(Vec<Piece>),
+    Paragraph(Vec<Piece>),
+    Code(String),
+    List(Vec<Vec<Piece>>),
+}
+
+/// Splits text into whitespace separated pieces, remembering whether a
+/// piece continues the word before it.
+fn push_text(pieces: &mut Vec<Piece>, text: &str, style: Style, space: &mut bool) {
+    for (i, word) in text.split(char::is_whitespace).enumerate() {
+        if i > 0 {
+            *space = true;
+        }
+        if word.is_empty() {
+            continue;
+        }
+        pieces.push(Piece {
+            space: *space && !pieces.is_empty(),
+            text: word.to_owned(),
+            style,
+        });
+        *space = false;
+    }
+}
+
+/// Parses the articles of a puzzle page into blocks of styled text.
+fn parse(html: &str) -> Vec<Block> {
+    let token_re = regex!(r"(?s)<(/?)([a-zA-Z0-9]+)[^>]*>|([^<]+)");
+
+    let mut blocks = Vec::new();
+    let mut pieces = Vec::new();
+    let mut items = Vec::new();
+    let mut code = String::new();
+    let mut in_pre = false;
+    let mut style = Style::default();
+    let mut space = false;
+
+    for token in token_re.captures_iter(html) {
+        if let Some(text) = token.get(3) {
+            let text = unescape(text.as_str());
+            if in_pre {
+                code += &text;
+            } else {
+                push_text(&mut pieces, &text, style, &mut space);
+            }
+            continue;
+        }
+
+        let closing = &token[1] == "/";
+        match (token[2].to_ascii_lowercase().as_str(), closing) {
+            ("pre", false) => in_pre = true,
+            ("pre", true) => {
+                in_pre = false;
+                blocks.push(Block::Code(std::mem::take(&mut code)));
+            }
+            (_, _) if in_pre => {}
+            ("em", _) => style.em = !closing,
+            ("code", _) => style.code = !closing,
+            ("h2" | "p" | "li", false) => {
+                pieces.clear();
+                space = false;
+            }
+            ("h2", true) => blocks.push(Block::Heading(std::mem::take(&mut pieces))),
+            ("p", true) => blocks.push(Block::Paragraph(std::mem::take(&mut pieces))),
+            ("li", true) => items.push(std::mem::take(&mut pieces)),
+            ("ul", true) => blocks.push(Block::List(std::mem::take(&mut items))),
+            _ => {}
+        }
+    }
+
+    blocks
+}
+
+fn paint(piece: &Piece) -> String {
+    let Piece { text, style, .. } = piece;
+    match (style.em, style.code) {
+        (true, true) => Paint::yellow(text).bold().to_string(),
+        (true, false) => Paint::new(text).bold().to_string(),
+        (false, true) => Paint::cyan(text).to_string(),
+        (false, false) => text.clone(),
+    }
+}
+
+/// Wraps pieces to `width` columns, indenting every line by `indent` and the
+/// first by `first` instead.
+fn wrap(pieces: &[Piece], width: usize, first: &str, indent: &str) -> String {
+    let mut out = first.to_owned();
+    let mut column = first.chars().count();
+    for (i, piece) in pieces.iter().enumerate() {
+        let word_len: usize = pieces[i..]
+            .iter()
+            .enumerate()
+            .take_while(|(j, p)| *j == 0 || !p.space)
+            .map(|(_, p)| p.text.chars().count())
+            .sum();
+        if piece.space {
+            if column + 1 + word_len > width {
+                out += "\n";
+                out += indent;
+                column = indent.chars().count();
+            } else {
+                out += " ";
+                column += 1;
+            }
+        }
+        out += &paint(piece);
+        column += piece.text.chars().count();
+    }
+    out
+}
+
+/// Renders blocks as wrapped and coloured terminal text.
+fn render_terminal(blocks: &[Block], width: usize) -> String {
+    let mut out = Vec::new();
+    for block in blocks {
+        match block {
+            Block::Heading(pieces) => {
+                if !out.is_empty() {
+                    out.push(Paint::fixed(245, "─".repeat(width)).to_string());
+                }
+                let text: Vec<_> = pieces.iter().map(|p| p.text.as_str()).collect();
+                out.push(Paint::green(text.join(" ")).bold().to_string());
+            }
+            Block::Paragraph(pieces) => out.push(wrap(pieces, width, "", "")),
+            Block::Code(code) => {
+                let lines: Vec<_> = code
+                    .trim_end_matches('\n')
+                    .lines()
+                    .map(|l| format!("    {}", Paint::fixed(250, l)))
+                    .collect();
+                out.push(lines.join("\n"));
+            }
+            Block::List(items) => {
+                let items: Vec<_> = items
+                    .iter()
+                    .map(|item| wrap(item, width, "  • ", "    "))
+                    .collect();
+                out.push(items.join("\n"));
+            }
+        }
+    }
+    out.join("\n\n")
+}
+
+fn markdown_inline(pieces: &[Piece]) -> String {
+    fn markers(style: Style) -> (&'static str, &'static str) {
+        match (style.em, style.code) {
+            (true, true) => ("*`", "`*"),
+            (true, false) => ("*", "*"),
+            (false, true) => ("`", "`"),
+            (false, false) => ("", ""),
+        }
+    }
+
+    let mut out = String::new();
+    let mut current = Style::default();
+    for piece in pieces {
+        if piece.style != current {
+            out += markers(current).1;
+        }
+        if piece.space {
+            out += " ";
+        }
+        if piece.style != current {
+            out += markers(piece.style).0;
+            current = piece.style;
+        }
+        out += &piece.text;
+    }
+    out += markers(current).1;
+    out
+}
+
+/// Renders blocks as Markdown.
+fn render_markdown(blocks: &[Block]) -> String {
+    let mut out = Vec::new();
+    for block in blocks {
+        match block {
+            Block::Heading(pieces) => out.push(format!("## {}", markdown_inline(pieces))),
+            Block::Paragraph(pieces) => out.push(markdown_inline(pieces)),
+            Block::Code(code) => out.push(format!("```\n{}\n```", code.trim_end_matches('\n'))),
+            Block::List(items) => {
+                let items: Vec<_> = items
+                    .iter()
+                    .map(|item| format!("- {}", markdown_inline(item)))
+                    .collect();
+                out.push(items.join("\n"));
+            }
+        }
+    }
+    out.join("\n\n") + "\n"
+}
+
+/// Returns the puzzle articles, downloading them unless they are cached.
+/// Pages are fetched again until they include the second part.
+fn articles(year: u32, day: u32, refresh: bool) -> Result<String> {
+    let article_re = regex!(r#"(?s)<article class="day-desc">.*?</article>"#);
+
+    let path = format!("input/{year:04}/{day:02}.html");
+    let cache_path = get_workspace_path().join(&path);
+    if !refresh {
+        if let Ok(cached) = fs::read_to_string(&cache_path) {
+            if article_re.find_iter(&cached).count() >= 2 {
+                return Ok(cached);
+            }
+        }
+    }
+
+    let html = http::download_page(
+        &format!("{}/{year}/day/{day}", base_url()),
+        profile::DEFAULT,
+    )?;
+    let articles: Vec<_> = article_re.find_iter(&html).map(|m| m.as_str()).collect();
+    if articles.is_empty() {
+        bail!("Could not find the puzzle description (year: {year:04}, day: {day:02}).");
+    }
+    let articles = articles.join("\n");
+
+    fs::create_dir_all(cache_path.parent().unwrap())?;
+    fs::write(&cache_path, &articles)?;
+    print("Cached", format!("puzzle description `{path}`"));
+
+    Ok(articles)
+}
+
+/// Prints the puzzle description, or writes it to `markdown` as Markdown.
+pub fn read(year: u32, day: u32, refresh: bool, markdown: Option<&Path>) -> Result<()> {
+    if !has_occurred(year, day) {
+        bail!("The puzzle has not been released yet (year: {year:04}, day: {day:02}).");
+    }
+
+    let blocks = parse(&articles(year, day, refresh)?);
+
+    match markdown {
+        Some(path) => {
+            fs::write(path, render_markdown(&blocks))?;
+            print(
+                "Exported",
+                format!("puzzle description to `{}`", path.display()),
+            );
+        }
+        None => {
+            let width = env::var("COLUMNS")
+                .ok()
+                .and_then(|c| c.parse().ok())
+                .unwrap_or(80usize)
+                .clamp(40, 100);
+            println!("{}", render_terminal(&blocks, width));
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ARTICLES: &str = r#"<article class="day-desc"><h2>--- Day 1: Trebuchet?! ---</h2><p>Something is wrong with global snow production, and you've been selected to take a look.</p>
+<p>The calibration values are <code>12</code>, <code>38</code>. Adding these together produces <code><em>142</em></code>.</p>
+<pre><code>1abc2
+pqr3stu8vwx
+</code></pre>
+<ul>
+<li>A <em>digit</em> and then some more text.</li>
+</ul>
+</article>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2><p>It looks like some of the digits are actually <em>spelled out with letters</em>: <code>one</code>.</p>
+</article>"#;
+
+    #[test]
+    fn markdown() {
+        assert_eq!(
+            render_markdown(&parse(ARTICLES)),
+            r#"## --- Day 1: Trebuchet?! ---
+
+Something is wrong with global snow production, and you've been selected to take a look.
+
+The calibration values are `12`, `38`. Adding these together produces *`142`*.
+
+```
+1abc2
+pqr3stu8vwx
+```
+
+- A *digit* and then some more text.
+
+## --- Part Two ---
+
+It looks like some of the digits are actually *spelled out with letters*: `one`.
+"#
+        );
+    }
+
+    #[test]
+    fn wrapping() {
+        let blocks = parse(ARTICLES);
+        let Block::Paragraph(pieces) = &blocks[1] else {
+            panic!("expected a paragraph")
+        };
+        let wrapped = wrap(pieces, 40, "", "");
+        assert_eq!(
+            regex!(r"\x1b\[[0-9;]*m").replace_all(&wrapped, ""),
+            "Something is wrong with global snow\nproduction, and you've been selected to\ntake a look."
+        );
+    }
+}
@@ -0,0 +1,142 @@
+//! Pick the template for a new day and work out what to fill it with.
+
+use std::{fmt, fs};
+
+use anyhow::{bail, Result};
+
+use crate::cargo::get_workspace_path;
+
+/// The template used when the workspace doesn't pick one.
+const DEFAULT_TEMPLATE: &str = include_str!("template.rs");
+
+/// The overall layout of a puzzle input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shape {
+    Empty,
+    Line { len: usize },
+    Grid { width: usize, height: usize },
+    Lines { count: usize },
+    Sections { count: usize },
+}
+
+impl Shape {
+    pub fn detect(input: &str) -> Self {
+        let input = input.trim_end_matches('\n');
+        if input.is_empty() {
+            return Self::Empty;
+        }
+        if input.contains("\n\n") {
+            return Self::Sections {
+                count: input.split("\n\n").count(),
+            };
+        }
+
+        let lines: Vec<_> = input.lines().collect();
+        let width = lines[0].chars().count();
+        if lines.len() == 1 {
+            Self::Line { len: width }
+        } else if width > 1
+            && lines
+                .iter()
+                .all(|l| l.chars().count() == width && !l.contains(' '))
+        {
+            Self::Grid {
+                width,
+                height: lines.len(),
+            }
+        } else {
+            Self::Lines { count: lines.len() }
+        }
+    }
+
+    /// The name of the template that usually suits this shape.
+    pub fn template(&self) -> Option<&'static str> {
+        match self {
+            Self::Grid { .. } => Some("grid"),
+            Self::Lines { .. } => Some("lines"),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Shape {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "empty"),
+            Self::Line { len } => write!(f, "a single line of {len} characters"),
+            Self::Grid { width, height } => write!(f, "a {width}×{height} grid"),
+            Self::Lines { count } => write!(f, "{count} lines"),
+            Self::Sections { count } => write!(f, "{count} sections separated by blank lines"),
+        }
+    }
+}
+
+/// The names of the templates in the workspace's `templates/` directory.
+pub fn templates() -> Vec<String> {
+    let mut names: Vec<_> = fs::read_dir(get_workspace_path().join("templates"))
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|e| {
+            let name = e.file_name().into_string().ok()?;
+            Some(name.strip_suffix(".rs")?.to_owned())
+        })
+        .collect();
+    names.sort();
+    names
+}
+
+/// Loads `templates/<name>.rs`, or `templates/default.rs` if there is one
+/// and the built in template if not.
+pub fn load_template(name: Option<&str>) -> Result<String> {
+    let dir = get_workspace_path().join("templates");
+    match name {
+        Some(name) => match fs::read_to_string(dir.join(format!("{name}.rs"))) {
+            Ok(template) => Ok(template),
+            Err(_) => bail!(
+                "There is no template named `{name}`, expected one of: {}.",
+                templates().join(", ")
+            ),
+        },
+        None => Ok(fs::read_to_string(dir.join("default.rs"))
+            .unwrap_or_else(|_| DEFAULT_TEMPLATE.to_owned())),
+    }
+}
+
+/// Writes `s` as a raw string literal.
+pub fn raw_string(s: &str) -> String {
+    let hashes = (0..)
+        .map(|n| "#".repeat(n))
+        .find(|h| !s.contains(&format!("\"{h}")))
+        .unwrap();
+    format!("r{hashes}\"{s}\"{hashes}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shapes() {
+        assert_eq!(Shape::detect(""), Shape::Empty);
+        assert_eq!(Shape::detect("(()(()(\n"), Shape::Line { len: 7 });
+        assert_eq!(
+            Shape::detect("#.#\n...\n.#.\n"),
+            Shape::Grid {
+                width: 3,
+                height: 3
+            }
+        );
+        assert_eq!(Shape::detect("1 2 3\n4 5 6\n"), Shape::Lines { count: 2 });
+        assert_eq!(
+            Shape::detect("seeds: 1\n\na-to-b:\n1 2 3\n\nb-to-c:\n4 5 6\n"),
+            Shape::Sections { count: 3 }
+        );
+    }
+
+    #[test]
+    fn raw_strings() {
+        assert_eq!(raw_string("abc"), r###"r"abc""###);
+        assert_eq!(raw_string(r#"say "hi""#), r###"r#"say "hi""#"###);
+    }
+}
@@ -0,0 +1,279 @@
+//! Choose which days the `--all` commands work on.
+
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    path::PathBuf,
+    process::Command,
+    time::Duration,
+};
+
+use anyhow::{bail, Result};
+use regex_macro::regex;
+use serde::{Deserialize, Serialize};
+
+use crate::cargo::{get_workspace_path, Binaries};
+
+/// A day or an inclusive range of days like `5..12`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Days {
+    pub start: u32,
+    pub end: u32,
+}
+
+impl Days {
+    /// The day, if this is only one day.
+    pub fn single(&self) -> Option<u32> {
+        (self.start == self.end).then_some(self.start)
+    }
+
+    pub fn contains(&self, day: u32) -> bool {
+        (self.start..=self.end).contains(&day)
+    }
+}
+
+impl argh::FromArgValue for Days {
+    fn from_arg_value(value: &str) -> Result<Self, String> {
+        let parse = |s: &str, default: u32| match s {
+            "" => Ok(default),
+            s => s.parse().map_err(|_| format!("`{s}` is not a day")),
+        };
+        match value.split_once("..") {
+            Some((start, end)) => {
+                let end = end.strip_prefix('=').unwrap_or(end);
+                let days = Self {
+                    start: parse(start, 1)?,
+                    end: parse(end, u32::MAX)?,
+                };
+                if days.start > days.end {
+                    return Err(format!("`{value}` starts after it ends"));
+                }
+                Ok(days)
+            }
+            None if value.is_empty() => Err("expected a day or a range of days".to_owned()),
+            None => {
+                let day = value
+                    .parse()
+                    .map_err(|_| format!("`{value}` is not a day"))?;
+                Ok(Self {
+                    start: day,
+                    end: day,
+                })
+            }
+        }
+    }
+}
+
+/// Parses a duration like `250ms` or `1.5s`.
+pub fn parse_duration(value: &str) -> Result<Duration, String> {
+    let split = value
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(value.len());
+    let (number, unit) = value.split_at(split);
+    let number: f64 = number
+        .parse()
+        .map_err(|_| format!("`{value}` is not a duration"))?;
+    let scale = match unit.trim() {
+        "ns" => 1e-9,
+        "us" | "µs" => 1e-6,
+        "ms" => 1e-3,
+        "s" | "" => 1.0,
+        _ => {
+            return Err(format!(
+                "unknown unit in `{value}`, expected ns, µs, ms or s"
+            ))
+        }
+    };
+    Duration::try_from_secs_f64(number * scale).map_err(|_| format!("`{value}` is too long"))
+}
+
+/// Whether an answer matched the one accepted by Advent of Code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Status {
+    Right,
+    Wrong,
+    Unknown,
+}
+
+/// What happened to a day the last time it was run.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LastRun {
+    pub parts: Vec<(Status, Duration)>,
+}
+
+fn last_runs_path() -> PathBuf {
+    get_workspace_path().join("target/advent/last-run.json")
+}
+
+pub fn load_last_runs() -> HashMap<String, LastRun> {
+    fs::read(last_runs_path())
+        .ok()
+        .and_then(|json| serde_json::from_slice(&json).ok())
+        .unwrap_or_default()
+}
+
+pub fn write_last_runs(runs: &HashMap<String, LastRun>) -> Result<()> {
+    let path = last_runs_path();
+    fs::create_dir_all(path.parent().unwrap())?;
+    fs::write(path, serde_json::to_string(runs)?)?;
+    Ok(())
+}
+
+/// The days touched by a set of changed files.
+#[derive(Debug, PartialEq, Eq)]
+enum Changed {
+    All,
+    Bins(HashSet<String>),
+}
+
+/// Maps changed paths to the days they affect. Changes to the library crates
+/// or the manifest affect every day.
+fn changed_bins<'a>(paths: impl IntoIterator<Item = &'a str>) -> Changed {
+    let day_re = regex!(r"^(?:input/)?(\d{4})/(\d{2})[./]");
+
+    let mut bins = HashSet::new();
+    for path in paths {
+        if path.starts_with("crates/") || path == "Cargo.toml" {
+            return Changed::All;
+        }
+        if let Some(caps) = day_re.captures(path) {
+            bins.insert(format!("{}{}", &caps[1], &caps[2]));
+        }
+    }
+    Changed::Bins(bins)
+}
+
+/// Lists the files that differ from `git_ref`, including untracked ones.
+fn changed_since(git_ref: &str) -> Result<Changed> {
+    let git = |args: &[&str]| -> Result<String> {
+        let output = Command::new("git")
+            .args(args)
+            .current_dir(get_workspace_path())
+            .output()?;
+        if !output.status.success() {
+            bail!(
+                "`git {}` failed: {}",
+                args.join(" "),
+                String::from_utf8_lossy(&output.stderr)
+                    .lines()
+                    .next()
+                    .unwrap_or_default()
+            );
+        }
+        Ok(String::from_utf8(output.stdout)?)
+    };
+
+    let diff = git(&["diff", "--name-only", git_ref, "--"])?;
+    let untracked = git(&["ls-files", "--others", "--exclude-standard"])?;
+    Ok(changed_bins(diff.lines().chain(untracked.lines())))
+}
+
+/// The filters given on the command line.
+#[derive(Debug, Default)]
+pub struct Selection {
+    pub year: Option<u32>,
+    pub days: Option<Days>,
+    pub bins: Vec<String>,
+    pub wrong: bool,
+    pub unknown: bool,
+    pub slower: Option<Duration>,
+    pub since: Option<String>,
+}
+
+impl Selection {
+    /// The names of the binaries that pass every filter.
+    pub fn select<'a>(&self, binaries: &'a Binaries) -> Result<Vec<&'a String>> {
+        let year_str = self.year.map(|y| format!("{y:04}")).unwrap_or_default();
+        let bins: HashSet<_> = self
+            .bins
+            .iter()
+            .flat_map(|b| b.split(','))
+            .map(str::trim)
+            .collect();
+        let changed = match &self.since {
+            Some(git_ref) => changed_since(git_ref)?,
+            None => Changed::All,
+        };
+        let last_runs = if self.wrong || self.unknown || self.slower.is_some() {
+            load_last_runs()
+        } else {
+            HashMap::new()
+        };
+
+        let status = |name: &str| {
+            // days that have never been run could be anything
+            let Some(last) = last_runs.get(name) else {
+                return true;
+            };
+            let has = |status| last.parts.iter().any(|(s, _)| *s == status);
+            let slow = |threshold| last.parts.iter().any(|(_, t)| *t > threshold);
+
+            let by_answer = match (self.wrong, self.unknown) {
+                (false, false) => true,
+                (wrong, unknown) => {
+                    (wrong && has(Status::Wrong)) || (unknown && has(Status::Unknown))
+                }
+            };
+            by_answer && self.slower.is_none_or(slow)
+        };
+
+        Ok(binaries
+            .bin
+            .iter()
+            .map(|b| &b.name)
+            .filter(|n| n.starts_with(&year_str))
+            .filter(|n| {
+                let day = n.get(4..).and_then(|d| d.parse().ok());
+                self.days
+                    .is_none_or(|days| day.is_some_and(|d| days.contains(d)))
+            })
+            .filter(|n| bins.is_empty() || bins.contains(n.as_str()))
+            .filter(|n| match &changed {
+                Changed::All => true,
+                Changed::Bins(changed) => changed.contains(*n),
+            })
+            .filter(|n| status(n))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use argh::FromArgValue;
+
+    #[test]
+    fn days() {
+        let days = |s| Days::from_arg_value(s);
+        assert_eq!(days("7"), Ok(Days { start: 7, end: 7 }));
+        assert_eq!(days("5..12"), Ok(Days { start: 5, end: 12 }));
+        assert_eq!(days("5..=12"), Ok(Days { start: 5, end: 12 }));
+        assert_eq!(days("..3"), Ok(Days { start: 1, end: 3 }));
+        assert!(days("20..").unwrap().contains(25));
+        assert!(days("x..3").is_err());
+        assert!(days("12..5").is_err());
+        assert!(days("").is_err());
+    }
+
+    #[test]
+    fn durations() {
+        assert_eq!(parse_duration("250ms"), Ok(Duration::from_millis(250)));
+        assert_eq!(parse_duration("1.5s"), Ok(Duration::from_millis(1500)));
+        assert_eq!(parse_duration("40µs"), Ok(Duration::from_micros(40)));
+        assert!(parse_duration("3 weeks").is_err());
+        assert!(parse_duration("1e300s").is_err());
+        assert!(parse_duration("99999999999999999999s").is_err());
+    }
+
+    #[test]
+    fn changed() {
+        assert_eq!(
+            changed_bins(["2022/17.rs", "input/2023/01.txt", "README.md"]),
+            Changed::Bins(HashSet::from(["202217".to_owned(), "202301".to_owned()]))
+        );
+        assert_eq!(
+            changed_bins(["2022/17.rs", "crates/prelude/src/grid.rs"]),
+            Changed::All
+        );
+    }
+}
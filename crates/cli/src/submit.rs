@@ -0,0 +1,222 @@
+use std::{process, time::Duration};
+
+use anyhow::{bail, Context, Result};
+use regex_macro::regex;
+
+use time::OffsetDateTime;
+
+use crate::{
+    base_url, ensure_default_input, ensure_input_fetched, http,
+    json::{Hint, RunSummary, Summary, WrongAnswer},
+    load_metadata, print, refresh_day_meta, set_input,
+    wait::format_hms,
+    write_metadata,
+};
+
+/// The verdict Advent of Code gave on a submission.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Right,
+    Wrong(Option<Hint>),
+    Wait(Duration),
+    AlreadySolved,
+    Unknown(String),
+}
+
+/// Reads the verdict out of the page returned after posting an answer.
+pub fn parse_verdict(html: &str) -> Verdict {
+    let article_re = regex!(r#"(?s)<article>(.*?)</article>"#);
+    let tag_re = regex!(r#"<[^>]*>"#);
+    let wait_re = regex!(r#"(?:(\d+)m )?(\d+)s left to wait"#);
+
+    let article = article_re
+        .captures(html)
+        .map(|c| c[1].to_owned())
+        .unwrap_or_else(|| html.to_owned());
+    let text = tag_re.replace_all(&article, "");
+
+    if text.contains("That's the right answer") {
+        Verdict::Right
+    } else if text.contains("That's not the right answer") {
+        let hint = if text.contains("too high") {
+            Some(Hint::TooHigh)
+        } else if text.contains("too low") {
+            Some(Hint::TooLow)
+        } else {
+            None
+        };
+        Verdict::Wrong(hint)
+    } else if text.contains("You gave an answer too recently") {
+        let secs = wait_re.captures(&text).map_or(60, |c| {
+            let mins: u64 = c.get(1).map_or(0, |m| m.as_str().parse().unwrap_or(0));
+            let secs: u64 = c[2].parse().unwrap_or(0);
+            mins * 60 + secs
+        });
+        Verdict::Wait(Duration::from_secs(secs))
+    } else if text.contains("You don't seem to be solving the right level") {
+        Verdict::AlreadySolved
+    } else {
+        Verdict::Unknown(text.trim().to_owned())
+    }
+}
+
+/// Runs the solution on the input of `profile` and returns its answer for
+/// `part`.
+fn compute_answer(year: u32, day: u32, profile: &str, part: u8) -> Result<String> {
+    ensure_input_fetched(year, day, profile)?;
+    ensure_default_input(year, day, profile)?;
+
+    let bin_name = format!("{year:04}{day:02}");
+    let mut cmd = process::Command::new(env!("CARGO"));
+    cmd.args(["run", "--quiet", "--features", "json", "--release"])
+        .args(["--bin", &bin_name, "--", "--output", "json"]);
+    set_input(&mut cmd, year, day, profile);
+    let output = cmd.output()?;
+    let runs: Vec<RunSummary> = match serde_json::from_slice(&output.stdout)? {
+        Summary::Run(runs) => runs,
+        _ => bail!("Expected run output from {bin_name}"),
+    };
+
+    let name = format!("Part {part}");
+    let run = runs
+        .into_iter()
+        .find(|r| r.name == name)
+        .with_context(|| format!("{bin_name} has no {name}"))?;
+    match run.result {
+        Some(answer) => answer
+            .submission()
+            .with_context(|| format!("Could not read the answer to {name}")),
+        None => bail!("{name} panicked"),
+    }
+}
+
+/// Submits `answer`, or the solution's answer, for `part` as `profile`,
+/// defaulting to the first part without an accepted answer.
+pub fn submit(
+    year: u32,
+    day: u32,
+    profile: &str,
+    part: Option<u8>,
+    answer: Option<&String>,
+) -> Result<()> {
+    let bin_name = format!("{year:04}{day:02}");
+
+    let mut metadata = load_metadata()?;
+    if metadata.day(profile, &bin_name).is_none() {
+        refresh_day_meta(&mut metadata, year, day, profile)?;
+    }
+    let meta = metadata.day(profile, &bin_name).unwrap();
+
+    let part = match part {
+        Some(part) if (1..=meta.parts).contains(&part) => part,
+        Some(part) => bail!("There is no part {part}."),
+        None if meta.answer1.is_none() => 1,
+        None if meta.answer2.is_none() && meta.parts > 1 => 2,
+        None => bail!("Every part has already been solved."),
+    };
+
+    let answer = match answer {
+        Some(answer) => answer.clone(),
+        None => compute_answer(year, day, profile, part)?,
+    };
+
+    if let Some(rejection) = meta.rejection(part, &answer) {
+        bail!("Not submitting `{answer}` for part {part}, it {rejection}.");
+    }
+
+    print(
+        "Submitting",
+        format!("`{answer}` for part {part} (year: {year:04}, day: {day:02})"),
+    );
+
+    let url = format!("{}/{year}/day/{day}/answer", base_url());
+    let level = part.to_string();
+    let html = http::post(
+        &url,
+        profile,
+        &[("level", level.as_str()), ("answer", answer.as_str())],
+    )?;
+
+    match parse_verdict(&html) {
+        Verdict::Right => {
+            let meta = metadata.days_mut(profile).get_mut(&bin_name).unwrap();
+            match part {
+                1 => meta.answer1 = Some(answer),
+                _ => meta.answer2 = Some(answer),
+            }
+            let taken = meta
+                .started
+                .map(|started| {
+                    let taken = (OffsetDateTime::now_utc() - started).unsigned_abs();
+                    format!(" in {}", format_hms(taken))
+                })
+                .unwrap_or_default();
+            write_metadata(&metadata)?;
+            print("Correct", format!("part {part} is solved ⭐{taken}"));
+        }
+        Verdict::Wrong(hint) => {
+            let meta = metadata.days_mut(profile).get_mut(&bin_name).unwrap();
+            meta.wrong.push(WrongAnswer {
+                part,
+                answer: answer.clone(),
+                hint,
+                time: OffsetDateTime::now_utc(),
+            });
+            write_metadata(&metadata)?;
+
+            let hint = match hint {
+                Some(Hint::TooHigh) => " (too high)",
+                Some(Hint::TooLow) => " (too low)",
+                None => "",
+            };
+            bail!("`{answer}` is not the right answer{hint}.");
+        }
+        Verdict::Wait(wait) => {
+            bail!("Answered too recently, wait {}s.", wait.as_secs());
+        }
+        Verdict::AlreadySolved => {
+            refresh_day_meta(&mut metadata, year, day, profile)?;
+            bail!("Part {part} is not the current level, it may already be solved.");
+        }
+        Verdict::Unknown(text) => bail!("Unexpected response:\n{text}"),
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn page(article: &str) -> String {
+        format!("<html><main>\n<article><p>{article}</p></article>\n</main></html>")
+    }
+
+    #[test]
+    fn verdicts() {
+        let right = page("That's the right answer!  You are <em>one gold star</em> closer.");
+        assert_eq!(parse_verdict(&right), Verdict::Right);
+
+        let high = page("That's not the right answer; your answer is too high.  If you're stuck, make sure you're using the full input data.");
+        assert_eq!(parse_verdict(&high), Verdict::Wrong(Some(Hint::TooHigh)));
+
+        let low = page("That's not the right answer; your answer is too low.");
+        assert_eq!(parse_verdict(&low), Verdict::Wrong(Some(Hint::TooLow)));
+
+        let wrong = page("That's not the right answer.  If you're stuck, ...");
+        assert_eq!(parse_verdict(&wrong), Verdict::Wrong(None));
+
+        let wait = page("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 4m 43s left to wait.");
+        assert_eq!(
+            parse_verdict(&wait),
+            Verdict::Wait(Duration::from_secs(283))
+        );
+
+        let wait = page("You gave an answer too recently.  You have 32s left to wait.");
+        assert_eq!(parse_verdict(&wait), Verdict::Wait(Duration::from_secs(32)));
+
+        let solved =
+            page("You don't seem to be solving the right level.  Did you already complete it?");
+        assert_eq!(parse_verdict(&solved), Verdict::AlreadySolved);
+    }
+}
@@ -17,16 +17,6 @@ fn main() {
     solution.cli();
 }
 
-#[test]
-fn example() {
-    let input = "";
-    assert_eq!(part1(input), 0);
-    assert_eq!(part2(input), 0);
-}
+{ examples }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input), 0);
-    assert_eq!(part2(input), 0);
-}
+advent::answers!(default_input, { year } / { day }, part1, part2);
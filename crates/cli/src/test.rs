@@ -0,0 +1,197 @@
+//! Run the tests of every day and report the results together.
+
+use std::process;
+
+use anyhow::Result;
+use regex_macro::regex;
+use yansi::Paint;
+
+use crate::{
+    cargo::{build_bins, get_binaries, get_workspace_path},
+    load_metadata,
+    select::Selection,
+    split_args,
+};
+
+const NAME_WIDTH: usize = 30;
+const COUNT_WIDTH: usize = 6;
+const FAILED_WIDTH: usize = 40;
+
+/// The outcome of each test in a libtest run.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct Report {
+    passed: Vec<String>,
+    failed: Vec<String>,
+    ignored: Vec<String>,
+}
+
+/// Reads the test results out of libtest's human readable output.
+fn parse_libtest(stdout: &str) -> Report {
+    let test_re = regex!(r"^test (\S+) \.\.\. (ok|FAILED|ignored)");
+
+    let mut report = Report::default();
+    for caps in stdout.lines().filter_map(|l| test_re.captures(l)) {
+        let name = caps[1].to_owned();
+        match &caps[2] {
+            "ok" => report.passed.push(name),
+            "FAILED" => report.failed.push(name),
+            _ => report.ignored.push(name),
+        }
+    }
+    report
+}
+
+fn line(left: char, mid: char, right: char) -> String {
+    format!(
+        "{left}────────────{pc:─^n_width$}─{mid}─{pc:─^c_width$}─{mid}─{pc:─^c_width$}─{mid}─{pc:─^c_width$}─{mid}─{pc:─^f_width$}─{right}",
+        pc = "",
+        n_width = NAME_WIDTH,
+        c_width = COUNT_WIDTH,
+        f_width = FAILED_WIDTH,
+    )
+}
+
+/// Builds the tests of every day once, runs each day's tests and prints a
+/// row per day with the names of any failing tests.
+pub fn test_all(selection: &Selection, args: &[String]) -> Result<()> {
+    let binaries = get_binaries()?;
+    let binaries = selection.select(&binaries)?;
+
+    let (cargo_args, bin_args) = split_args(args);
+    let mut build_args = vec!["test", "--release", "--no-run", "--no-fail-fast"];
+    build_args.extend(cargo_args.iter().map(String::as_str));
+    let executables = build_bins(&build_args, &binaries)?;
+
+    let metadata = load_metadata()?;
+
+    let mut success = true;
+
+    println!("{}", line('╭', '┬', '╮'));
+    println!(
+        "│ {:^n_width$} │ {:^c_width$} │ {:^c_width$} │ {:^c_width$} │ {:^f_width$} │",
+        Paint::new("Puzzle").bold(),
+        Paint::new("Passed").bold(),
+        Paint::new("Failed").bold(),
+        Paint::new("Ignore").bold(),
+        Paint::new("Failing tests").bold(),
+        n_width = NAME_WIDTH + 11,
+        c_width = COUNT_WIDTH,
+        f_width = FAILED_WIDTH,
+    );
+    println!("{}", line('├', '┼', '┤'));
+
+    let mut prev_year = "";
+
+    for bin in binaries {
+        let (year, day) = bin.split_at(4);
+
+        if year != prev_year && !prev_year.is_empty() {
+            println!("{}", line('├', '┼', '┤'));
+        }
+        prev_year = year;
+
+        let report = match executables.get(bin) {
+            Some(executable) => {
+                let output = process::Command::new(executable)
+                    .args(bin_args)
+                    .current_dir(get_workspace_path())
+                    .output()?;
+                let report = parse_libtest(&String::from_utf8_lossy(&output.stdout));
+                success = success && output.status.success();
+                Some(report)
+            }
+            None => {
+                success = false;
+                None
+            }
+        };
+
+        let mut puzzle_name = metadata
+            .days
+            .get(bin)
+            .and_then(|m| m.name.clone())
+            .unwrap_or_default();
+        if puzzle_name.len() > NAME_WIDTH {
+            puzzle_name = puzzle_name.chars().take(NAME_WIDTH - 1).collect();
+            puzzle_name.push('…');
+        };
+
+        let (passed, failed, ignored, failing) = match &report {
+            Some(report) => {
+                let mut failing = report.failed.join(", ");
+                if failing.chars().count() > FAILED_WIDTH {
+                    failing = failing.chars().take(FAILED_WIDTH - 1).collect();
+                    failing.push('…');
+                }
+                (
+                    Paint::green(report.passed.len().to_string()),
+                    if report.failed.is_empty() {
+                        Paint::fixed(245, "0".to_owned())
+                    } else {
+                        Paint::red(report.failed.len().to_string()).bold()
+                    },
+                    Paint::fixed(245, report.ignored.len().to_string()),
+                    Paint::red(failing),
+                )
+            }
+            None => (
+                Paint::fixed(245, "-".to_owned()),
+                Paint::fixed(245, "-".to_owned()),
+                Paint::fixed(245, "-".to_owned()),
+                Paint::red("failed to build".to_owned()).bold(),
+            ),
+        };
+
+        println!(
+            "│ {}: {:<n_width$} │ {:>c_width$} │ {:>c_width$} │ {:>c_width$} │ {:<f_width$} │",
+            Paint::cyan(format!("{year} / {day}")).bold(),
+            puzzle_name,
+            passed,
+            failed,
+            ignored,
+            failing,
+            n_width = NAME_WIDTH,
+            c_width = COUNT_WIDTH,
+            f_width = FAILED_WIDTH,
+        );
+    }
+
+    println!("{}", line('╰', '┴', '╯'));
+
+    if !success {
+        process::exit(1);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn libtest_output() {
+        let stdout = "
+running 4 tests
+test answers ... FAILED
+test tests::example ... ok
+test tests::slow ... ignored, takes a while
+test tests::parse ... ok
+
+failures:
+
+---- answers stdout ----
+thread 'answers' panicked at src/snapshot.rs:40:9:
+
+test result: FAILED. 2 passed; 1 failed; 1 ignored; 0 measured; 0 filtered out; finished in 0.01s
+";
+        assert_eq!(
+            parse_libtest(stdout),
+            Report {
+                passed: vec!["tests::example".into(), "tests::parse".into()],
+                failed: vec!["answers".into()],
+                ignored: vec!["tests::slow".into()],
+            }
+        );
+    }
+}
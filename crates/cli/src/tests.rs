@@ -0,0 +1,466 @@
+//! Tests for the CLI against a local stand-in for Advent of Code and scratch
+//! workspaces.
+
+use std::{
+    cell::Cell,
+    collections::HashMap,
+    env,
+    io::{BufRead, BufReader, Read, Write},
+    net::{TcpListener, TcpStream},
+    path::{Path, PathBuf},
+    process::Command,
+    sync::{Arc, Mutex, MutexGuard, OnceLock},
+    thread,
+    time::Duration,
+};
+
+use time::OffsetDateTime;
+
+use crate::{
+    cargo::{get_binaries, get_workspace_path, shim, sync_bins, Binary},
+    ensure_default_input, ensure_input_fetched, get_day_meta,
+    json::Hint,
+    load_metadata, meta,
+    profile::{self, DEFAULT},
+    puzzle::Example,
+    render_template,
+    scaffold::Shape,
+    select::Days,
+    submit::submit,
+    wait::{self, Clock},
+};
+
+const INPUT: &str = "(()(()(\n";
+
+const PUZZLE: &str = r#"<!DOCTYPE html>
+<html lang="en-us">
+<body>
+<main>
+<article class="day-desc"><h2>--- Day 1: Santa&apos;s Lisp ---</h2><p>Santa is trying to deliver presents.</p>
+<pre><code>(())</code></pre>
+</article>
+<p>Your puzzle answer was <code>232</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2><p>Now, given the same instructions.</p>
+</article>
+<p>Your puzzle answer was <code>1783</code>.</p>
+<p>Both parts of this puzzle are complete! They provide two gold stars: **</p>
+</main>
+</body>
+</html>
+"#;
+
+const UNSOLVED: &str = r#"<main>
+<article class="day-desc"><h2>--- Day 3: Perfectly Spherical Houses in a Vacuum ---</h2></article>
+</main>"#;
+
+const RIGHT: &str = "<main><article><p>That's the right answer!  You are <span class=\"day-success\">one gold star</span> closer.</p></article></main>";
+const TOO_HIGH: &str = "<main><article><p>That's not the right answer; your answer is too high.  Please wait one minute before trying again.</p></article></main>";
+
+/// A request received by the fixture server.
+#[derive(Debug, Clone)]
+struct Request {
+    method: String,
+    path: String,
+    cookie: Option<String>,
+    if_none_match: Option<String>,
+    body: String,
+}
+
+struct Fixture {
+    requests: Arc<Mutex<Vec<Request>>>,
+    workspace: PathBuf,
+}
+
+impl Fixture {
+    fn requests(&self, path: &str) -> Vec<Request> {
+        let requests = self.requests.lock().unwrap();
+        requests
+            .iter()
+            .filter(|r| r.path == path)
+            .cloned()
+            .collect()
+    }
+}
+
+const ETAG: &str = "\"v1\"";
+
+/// The status, extra headers and body to reply with. `seen` is how many
+/// times the path was requested before.
+fn respond(request: &Request, seen: usize) -> (u16, String, String) {
+    let ok = |body: &str| (200, String::new(), body.to_owned());
+    match request.path.as_str() {
+        "/2015/day/1" if request.if_none_match.as_deref() == Some(ETAG) => {
+            (304, String::new(), String::new())
+        }
+        "/2015/day/1" => (200, format!("ETag: {ETAG}\r\n"), PUZZLE.to_owned()),
+        "/2015/day/3" | "/2015/day/4" => ok(UNSOLVED),
+        "/2015/day/1/input" | "/2015/day/5/input" => ok(INPUT),
+        "/2015/day/2/input" => (404, String::new(), include_str!("error.txt").to_owned()),
+        "/2015/day/3/answer" | "/2015/day/4/answer" if request.body.contains("answer=1234") => {
+            ok(RIGHT)
+        }
+        "/2015/day/3/answer" | "/2015/day/4/answer" => ok(TOO_HIGH),
+        "/2015/day/6/input" => (
+            400,
+            String::new(),
+            "Puzzle inputs differ by user.  Please log in to get your puzzle input.".to_owned(),
+        ),
+        "/2015/day/7/input" => (302, "Location: /auth/login\r\n".to_owned(), String::new()),
+        "/2015/day/8/input" if seen == 0 => (503, String::new(), "Unavailable".to_owned()),
+        "/2015/day/8/input" => ok(INPUT),
+        "/2015/day/9/input" if seen == 0 => {
+            (404, String::new(), include_str!("error.txt").to_owned())
+        }
+        "/2015/day/9/input" => ok(INPUT),
+        _ => (404, String::new(), "404 Not Found".to_owned()),
+    }
+}
+
+fn handle(stream: TcpStream, requests: &Mutex<Vec<Request>>) -> Option<()> {
+    let mut reader = BufReader::new(stream.try_clone().ok()?);
+
+    let mut line = String::new();
+    reader.read_line(&mut line).ok()?;
+    let mut parts = line.split_whitespace();
+    let method = parts.next()?.to_owned();
+    let path = parts.next()?.to_owned();
+
+    let mut headers = HashMap::new();
+    loop {
+        let mut line = String::new();
+        reader.read_line(&mut line).ok()?;
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        let (name, value) = line.split_once(':')?;
+        headers.insert(name.trim().to_ascii_lowercase(), value.trim().to_owned());
+    }
+
+    let len = headers
+        .get("content-length")
+        .map_or(0, |l| l.parse().unwrap_or(0));
+    let mut body = vec![0; len];
+    reader.read_exact(&mut body).ok()?;
+    let body = String::from_utf8_lossy(&body).into_owned();
+
+    let request = Request {
+        method,
+        path,
+        cookie: headers.get("cookie").cloned(),
+        if_none_match: headers.get("if-none-match").cloned(),
+        body,
+    };
+    let mut requests = requests.lock().unwrap();
+    let seen = requests.iter().filter(|r| r.path == request.path).count();
+    let (status, extra, response) = respond(&request, seen);
+    requests.push(request);
+    drop(requests);
+
+    let mut stream = stream;
+    write!(
+        stream,
+        "HTTP/1.1 {status} OK\r\n{extra}Content-Length: {}\r\nConnection: close\r\n\r\n{response}",
+        response.len()
+    )
+    .ok()
+}
+
+/// Starts the fixture server and points the CLI at it and at a scratch
+/// workspace. Tests hold the returned guard since they share the workspace.
+fn fixture() -> MutexGuard<'static, Fixture> {
+    static FIXTURE: OnceLock<Mutex<Fixture>> = OnceLock::new();
+
+    let fixture = FIXTURE.get_or_init(|| {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let requests = Arc::new(Mutex::new(Vec::new()));
+
+        let server_requests = requests.clone();
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                handle(stream, &server_requests);
+            }
+        });
+
+        let workspace = env::temp_dir().join(format!("advent-cli-test-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&workspace);
+        std::fs::create_dir_all(&workspace).unwrap();
+        std::fs::write(
+            workspace.join("advent.toml"),
+            "[profiles.alice]\nsession = \"alice-session\"\n",
+        )
+        .unwrap();
+
+        env::set_var("AOC_BASE_URL", format!("http://{addr}"));
+        env::set_var("AOC_SESSION_ID", "fixture-session");
+        env::set_var("ADVENT_WORKSPACE_DIR", &workspace);
+        env::set_var("ADVENT_REQUEST_INTERVAL", "0s");
+
+        Mutex::new(Fixture {
+            requests,
+            workspace,
+        })
+    });
+
+    fixture.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+#[test]
+fn fetches_input_once() {
+    let fixture = fixture();
+    assert_eq!(get_workspace_path(), fixture.workspace);
+
+    ensure_input_fetched(2015, 1, DEFAULT).unwrap();
+    ensure_input_fetched(2015, 1, DEFAULT).unwrap();
+
+    let input = std::fs::read_to_string(fixture.workspace.join("input/2015/01.txt")).unwrap();
+    assert_eq!(input, INPUT);
+
+    let requests = fixture.requests("/2015/day/1/input");
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].method, "GET");
+    assert_eq!(
+        requests[0].cookie.as_deref(),
+        Some("session=fixture-session")
+    );
+}
+
+#[test]
+fn refuses_locked_input() {
+    let fixture = fixture();
+
+    assert!(ensure_input_fetched(2015, 2, DEFAULT).is_err());
+    assert!(!fixture.workspace.join("input/2015/02.txt").exists());
+}
+
+#[test]
+fn scrapes_day_meta() {
+    let fixture = fixture();
+
+    for _ in 0..2 {
+        let meta = get_day_meta(2015, 1, DEFAULT).unwrap();
+        assert_eq!(meta.name.as_deref(), Some("Santa's Lisp"));
+        assert_eq!(meta.answer1.as_deref(), Some("232"));
+        assert_eq!(meta.answer2.as_deref(), Some("1783"));
+    }
+
+    // the second time the page comes from the cache
+    let requests = fixture.requests("/2015/day/1");
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[0].if_none_match, None);
+    assert_eq!(requests[1].if_none_match.as_deref(), Some(ETAG));
+}
+
+#[test]
+fn detects_expired_sessions() {
+    let fixture = fixture();
+
+    for day in [6, 7] {
+        let e = ensure_input_fetched(2015, day, DEFAULT).unwrap_err();
+        assert!(e.to_string().contains("expired"), "{e}");
+    }
+    assert!(!fixture.workspace.join("input/2015/06.txt").exists());
+    assert!(!fixture.workspace.join("input/2015/07.txt").exists());
+}
+
+#[test]
+fn retries_server_errors() {
+    let fixture = fixture();
+
+    ensure_input_fetched(2015, 8, DEFAULT).unwrap();
+    assert_eq!(fixture.requests("/2015/day/8/input").len(), 2);
+    let input = std::fs::read_to_string(fixture.workspace.join("input/2015/08.txt")).unwrap();
+    assert_eq!(input, INPUT);
+}
+
+#[test]
+fn submits_answers() {
+    let fixture = fixture();
+
+    let wrong = "2000".to_owned();
+    assert!(submit(2015, 4, DEFAULT, Some(1), Some(&wrong)).is_err());
+    let requests = fixture.requests("/2015/day/4/answer");
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].method, "POST");
+    assert_eq!(requests[0].body, "level=1&answer=2000");
+
+    // known to be too high so it never reaches the server
+    let higher = "3000".to_owned();
+    assert!(submit(2015, 4, DEFAULT, Some(1), Some(&higher)).is_err());
+    assert_eq!(fixture.requests("/2015/day/4/answer").len(), 1);
+
+    let right = "1234".to_owned();
+    submit(2015, 4, DEFAULT, Some(1), Some(&right)).unwrap();
+
+    let metadata = load_metadata().unwrap();
+    let meta = &metadata.days["201504"];
+    assert_eq!(meta.answer1.as_deref(), Some("1234"));
+    assert_eq!(meta.wrong.len(), 1);
+    assert_eq!(meta.wrong[0].answer, "2000");
+    assert_eq!(meta.wrong[0].hint, Some(Hint::TooHigh));
+}
+
+#[test]
+fn keeps_profiles_apart() {
+    let fixture = fixture();
+    assert_eq!(profile::names(), ["default", "alice"]);
+
+    ensure_input_fetched(2015, 5, "alice").unwrap();
+    assert!(fixture.workspace.join("input/2015/05/alice.txt").exists());
+    assert!(!fixture.workspace.join("input/2015/05.txt").exists());
+    let requests = fixture.requests("/2015/day/5/input");
+    assert_eq!(requests[0].cookie.as_deref(), Some("session=alice-session"));
+
+    // but the default input is still needed to build
+    ensure_default_input(2015, 5, "alice").unwrap();
+    let requests = fixture.requests("/2015/day/5/input");
+    assert_eq!(
+        requests[1].cookie.as_deref(),
+        Some("session=fixture-session")
+    );
+    assert!(fixture.workspace.join("input/2015/05.txt").exists());
+
+    let right = "1234".to_owned();
+    submit(2015, 3, "alice", Some(1), Some(&right)).unwrap();
+    let metadata = load_metadata().unwrap();
+    assert!(metadata.day(DEFAULT, "201503").is_none());
+    assert_eq!(
+        metadata.day("alice", "201503").unwrap().answer1.as_deref(),
+        Some("1234")
+    );
+
+    assert!(profile::check("bob").is_err());
+}
+
+#[test]
+fn discovers_bins() {
+    let fixture = fixture();
+    let dir = fixture.workspace.join("2016");
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("01.rs"), "fn main() {}\n").unwrap();
+    std::fs::write(dir.join("notes.rs"), "").unwrap();
+    sync_bins().unwrap();
+
+    let names: Vec<_> = get_binaries()
+        .unwrap()
+        .bin
+        .into_iter()
+        .map(|b| b.name)
+        .collect();
+    assert_eq!(names, ["201601"]);
+    let shim = fixture.workspace.join("src/bin/201601.rs");
+    assert!(std::fs::read_to_string(&shim)
+        .unwrap()
+        .contains(r#"include!("../../2016/01.rs");"#));
+
+    // shims written by hand are left alone
+    let manual = fixture.workspace.join("src/bin/tool.rs");
+    std::fs::write(&manual, "fn main() {}\n").unwrap();
+    std::fs::remove_file(dir.join("01.rs")).unwrap();
+    sync_bins().unwrap();
+    assert!(!shim.exists());
+    assert!(manual.exists());
+}
+
+/// A clock that only moves when slept on.
+struct FakeClock(Cell<OffsetDateTime>);
+
+impl Clock for FakeClock {
+    fn now(&self) -> OffsetDateTime {
+        self.0.get()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        self.0.set(self.0.get() + duration);
+    }
+}
+
+#[test]
+fn waits_for_unlock() {
+    let fixture = fixture();
+    let unlock =
+        crate::calendar::unlock_time(2015, 9, crate::config::config().timezone.value).unwrap();
+    let clock = FakeClock(Cell::new(unlock - Duration::from_millis(2500)));
+
+    assert!(wait::unlock(&clock, 2023, 40, DEFAULT).is_err());
+    wait::unlock(&clock, 2015, 9, DEFAULT).unwrap();
+    assert!(clock.now() > unlock);
+    assert!(fixture.workspace.join("input/2015/09.txt").exists());
+    // the first try was too early
+    assert_eq!(fixture.requests("/2015/day/9/input").len(), 2);
+
+    wait::start_timer(2015, 9, DEFAULT, clock.now()).unwrap();
+    let started = load_metadata().unwrap().days["201509"].started;
+    assert_eq!(started, Some(clock.now()));
+}
+
+#[test]
+fn meta_clamps_open_ranges() {
+    let _fixture = fixture();
+    let open: Days = argh::FromArgValue::from_arg_value("20..").unwrap();
+    assert!(meta::released(2015, Some(open)).eq(20..=25));
+    assert_eq!(meta::released(2025, Some(open)).count(), 0);
+    assert!(meta::released(2025, None).eq(1..=12));
+}
+
+#[test]
+fn templates_build_through_shims() {
+    let repo = Path::new(env!("CARGO_WORKSPACE_DIR"));
+    let dir = env::temp_dir().join(format!("advent-cli-templates-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(dir.join("src/bin")).unwrap();
+    std::fs::create_dir_all(dir.join("2015")).unwrap();
+    std::fs::create_dir_all(dir.join("input/2015")).unwrap();
+    std::fs::write(
+        dir.join("Cargo.toml"),
+        format!(
+            "[package]\nname = \"templates\"\nversion = \"0.0.0\"\nedition = \"2021\"\n\n\
+             [workspace]\n\n[dependencies]\nadvent = {{ path = {:?} }}\n",
+            repo.join("crates/advent")
+        ),
+    )
+    .unwrap();
+
+    let mut templates = vec![include_str!("template.rs").to_owned()];
+    for entry in std::fs::read_dir(repo.join("templates")).unwrap() {
+        templates.push(std::fs::read_to_string(entry.unwrap().path()).unwrap());
+    }
+    // an answer that isn't a number can't be compared with what parts return
+    let examples = [Example {
+        input: "#.\n.#\n".to_owned(),
+        answers: [Some("2".to_owned()), Some("AB".to_owned())],
+    }];
+    for (day, template) in (1..).zip(&templates) {
+        let source = render_template(template, 2015, day, Some("Test"), Shape::Empty, &examples);
+        let binary = Binary {
+            name: format!("2015{day:02}"),
+            path: format!("2015/{day:02}.rs").into(),
+        };
+        std::fs::write(dir.join(&binary.path), source).unwrap();
+        std::fs::write(dir.join(format!("input/2015/{day:02}.txt")), "#.\n.#\n").unwrap();
+        std::fs::write(
+            dir.join(format!("input/2015/{day:02}.example1.txt")),
+            &examples[0].input,
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join(format!("src/bin/{}.rs", binary.name)),
+            shim(&binary),
+        )
+        .unwrap();
+    }
+
+    // its own target directory since the one running the tests is locked
+    let output = Command::new(env!("CARGO"))
+        .args(["check", "--quiet", "--offline", "--bins", "--tests"])
+        .env("CARGO_TARGET_DIR", repo.join("target/advent/templates"))
+        .current_dir(&dir)
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
@@ -0,0 +1,219 @@
+//! Wait for a puzzle to unlock, then get everything ready to solve it.
+
+use std::{
+    io::{self, IsTerminal, Write},
+    thread,
+    time::Duration,
+};
+
+use anyhow::{bail, Result};
+use rand::Rng;
+use time::{OffsetDateTime, UtcOffset};
+use yansi::Paint;
+
+use crate::{
+    calendar::{calendar, unlock_time},
+    cargo::get_bin_name,
+    config::config,
+    ensure_input_fetched,
+    json::DayMeta,
+    load_metadata, new, print, warn, write_metadata,
+};
+
+/// How many times the input is asked for before giving up.
+const ATTEMPTS: u32 = 10;
+
+/// Where the time comes from, so waiting can be tested without waiting.
+pub trait Clock {
+    fn now(&self) -> OffsetDateTime;
+    fn sleep(&self, duration: Duration);
+}
+
+/// The clock on the wall.
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> OffsetDateTime {
+        OffsetDateTime::now_utc()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        thread::sleep(duration)
+    }
+}
+
+/// The first puzzle of `year` to unlock after `now`, if any are left.
+pub fn next_unlock_in(year: u32, now: OffsetDateTime, offset: UtcOffset) -> Option<u32> {
+    (1..=calendar(year).days)
+        .find(|&day| unlock_time(year, day, offset).is_some_and(|unlock| unlock > now))
+}
+
+/// The first puzzle to unlock after `now`.
+pub fn next_unlock(now: OffsetDateTime, offset: UtcOffset) -> (u32, u32) {
+    let year = now.to_offset(offset).year() as u32;
+    match next_unlock_in(year, now, offset) {
+        Some(day) => (year, day),
+        None => (year + 1, 1),
+    }
+}
+
+/// Formats a duration like `2d 03:04:05`, rounding up to the second.
+pub fn format_hms(duration: Duration) -> String {
+    let secs = duration.as_secs() + u64::from(duration.subsec_nanos() > 0);
+    let (days, secs) = (secs / 86400, secs % 86400);
+    let hms = format!(
+        "{:02}:{:02}:{:02}",
+        secs / 3600,
+        secs % 3600 / 60,
+        secs % 60
+    );
+    match days {
+        0 => hms,
+        days => format!("{days}d {hms}"),
+    }
+}
+
+/// Counts down to `unlock`, in place if stdout is a terminal.
+fn countdown(clock: &impl Clock, year: u32, day: u32, unlock: OffsetDateTime) {
+    let terminal = io::stdout().is_terminal();
+    let message = |left| {
+        format!(
+            "for {year:04} day {day:02}, unlocks in {}",
+            format_hms(left)
+        )
+    };
+
+    let mut waited = false;
+    loop {
+        let now = clock.now();
+        if now >= unlock {
+            break;
+        }
+        let left = (unlock - now).unsigned_abs();
+        if terminal {
+            print!(
+                "\r\x1b[2K{:>12} {}",
+                Paint::green("Waiting").bold(),
+                message(left)
+            );
+            _ = io::stdout().flush();
+        } else if !waited {
+            print("Waiting", message(left));
+        }
+        waited = true;
+        // tick on the second so the countdown doesn't skip any
+        clock.sleep(match left.subsec_nanos() {
+            0 => Duration::from_secs(1),
+            nanos => Duration::from_nanos(nanos.into()),
+        });
+    }
+    if terminal && waited {
+        println!();
+    }
+}
+
+/// Waits for a day to unlock and downloads its input. The input isn't
+/// always served the moment it unlocks so it is retried, with jitter so
+/// everyone waiting doesn't ask at once.
+pub fn unlock(clock: &impl Clock, year: u32, day: u32, profile: &str) -> Result<()> {
+    let days = calendar(year).days;
+    let Some(unlock) = unlock_time(year, day, config().timezone.value) else {
+        bail!("{year} has {days} days, there is no day {day}.");
+    };
+    countdown(clock, year, day, unlock);
+
+    let mut rng = rand::thread_rng();
+    let mut jitter = |ms: u64| Duration::from_millis(rng.gen_range(ms..ms * 3));
+
+    clock.sleep(jitter(500));
+    let mut attempt = 1;
+    loop {
+        match ensure_input_fetched(year, day, profile) {
+            Ok(()) => return Ok(()),
+            Err(e) if attempt < ATTEMPTS => {
+                let delay = jitter(1000);
+                warn(
+                    "Retrying",
+                    format!("in {:.1}s after: {e}", delay.as_secs_f64()),
+                );
+                clock.sleep(delay);
+                attempt += 1;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// Records when solving a day started, unless it already has.
+pub fn start_timer(year: u32, day: u32, profile: &str, now: OffsetDateTime) -> Result<()> {
+    let mut metadata = load_metadata()?;
+    let meta = metadata
+        .days_mut(profile)
+        .entry(get_bin_name(year, day))
+        .or_insert_with(|| DayMeta::for_day(year, day));
+    if meta.started.is_none() {
+        meta.started = Some(now);
+        write_metadata(&metadata)?;
+    }
+    print(
+        "Started",
+        "the solve timer, it is shown when an answer is accepted",
+    );
+    Ok(())
+}
+
+/// Waits for the next puzzle, the next one of `year` or the given day, then
+/// downloads its input, starts timing the solve and scaffolds it.
+pub fn wait(
+    clock: &impl Clock,
+    year: Option<u32>,
+    day: Option<u32>,
+    profile: &str,
+    template: Option<&str>,
+) -> Result<()> {
+    let offset = config().timezone.value;
+    let (year, day) = match (year, day) {
+        (Some(year), Some(day)) => (year, day),
+        (Some(year), None) => match next_unlock_in(year, clock.now(), offset) {
+            Some(day) => (year, day),
+            None => bail!("Every puzzle of {year:04} has already unlocked."),
+        },
+        (None, _) => next_unlock(clock.now(), offset),
+    };
+
+    unlock(clock, year, day, profile)?;
+    start_timer(year, day, profile, clock.now())?;
+    new(year, day, profile, template)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::{datetime, offset};
+
+    #[test]
+    fn next_unlocks() {
+        let est = offset!(-5);
+        assert_eq!(next_unlock(datetime!(2023-06-01 0:00 UTC), est), (2023, 1));
+        // still the 3rd in New York
+        assert_eq!(next_unlock(datetime!(2023-12-04 4:59 UTC), est), (2023, 4));
+        assert_eq!(next_unlock(datetime!(2023-12-04 5:00 UTC), est), (2023, 5));
+        assert_eq!(next_unlock(datetime!(2023-12-25 5:00 UTC), est), (2024, 1));
+        assert_eq!(next_unlock(datetime!(2025-12-12 5:00 UTC), est), (2026, 1));
+        assert_eq!(
+            next_unlock_in(2026, datetime!(2025-12-12 5:00 UTC), est),
+            Some(1)
+        );
+        assert_eq!(
+            next_unlock_in(2025, datetime!(2025-12-12 5:00 UTC), est),
+            None
+        );
+    }
+
+    #[test]
+    fn hms() {
+        assert_eq!(format_hms(Duration::from_secs(0)), "00:00:00");
+        assert_eq!(format_hms(Duration::from_millis(61_500)), "00:01:02");
+        assert_eq!(format_hms(Duration::from_secs(90061)), "1d 01:01:01");
+    }
+}
@@ -0,0 +1,86 @@
+//! Hooks that let the runner change what `include_input!` returns.
+
+use std::{cell::Cell, env, fs, sync::OnceLock};
+
+thread_local! {
+    static FRACTION: Cell<Option<f64>> = const { Cell::new(None) };
+    static LAST: Cell<Option<&'static str>> = const { Cell::new(None) };
+}
+
+/// Makes `include_input!` return only this fraction of the input's lines.
+pub fn set_fraction(fraction: Option<f64>) {
+    FRACTION.with(|f| f.set(fraction));
+    LAST.with(|l| l.set(None));
+}
+
+/// Returns how many lines the last `include_input!` produced.
+pub fn last_lines() -> Option<usize> {
+    LAST.with(Cell::get).map(|input| input.lines().count())
+}
+
+/// Called by `include_input!` with the full puzzle input.
+pub fn apply(input: &'static str) -> &'static str {
+    apply_with("", input)
+}
+
+/// Called by `include_input!` with an input it prefixed with `extra`.
+pub fn apply_with(extra: &str, input: &'static str) -> &'static str {
+    let input = replacement(extra).unwrap_or(input);
+    let input = match FRACTION.with(Cell::get) {
+        Some(fraction) => prefix_lines(input, fraction),
+        None => input,
+    };
+    LAST.with(|l| l.set(Some(input)));
+    input
+}
+
+/// The input in the file at `ADVENT_INPUT`, if it is set, so one build can
+/// be run against the inputs of several accounts. It is read once, so `extra`
+/// must be the same every time.
+fn replacement(extra: &str) -> Option<&'static str> {
+    static REPLACEMENT: OnceLock<Option<&'static str>> = OnceLock::new();
+    *REPLACEMENT.get_or_init(|| {
+        let path = env::var_os("ADVENT_INPUT")?;
+        let input = fs::read_to_string(&path).unwrap_or_else(|e| {
+            panic!(
+                "failed to read `ADVENT_INPUT` {}: {e}",
+                path.to_string_lossy()
+            )
+        });
+        Some(Box::leak(format!("{extra}{input}").into_boxed_str()))
+    })
+}
+
+/// Returns the first `fraction` of the lines of `input`, rounding up.
+pub fn prefix_lines(input: &str, fraction: f64) -> &str {
+    let newlines = input.bytes().filter(|&b| b == b'\n').count();
+    let total = newlines + usize::from(!input.is_empty() && !input.ends_with('\n'));
+    let keep = (total as f64 * fraction.clamp(0.0, 1.0)).ceil() as usize;
+    if keep == 0 {
+        return "";
+    }
+    match input
+        .bytes()
+        .enumerate()
+        .filter(|&(_, b)| b == b'\n')
+        .nth(keep - 1)
+    {
+        Some((i, _)) => &input[..=i],
+        None => input,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prefix() {
+        let input = "a\nb\nc\nd\n";
+        assert_eq!(prefix_lines(input, 0.0), "");
+        assert_eq!(prefix_lines(input, 0.25), "a\n");
+        assert_eq!(prefix_lines(input, 0.3), "a\nb\n");
+        assert_eq!(prefix_lines(input, 1.0), input);
+        assert_eq!(prefix_lines("a\nb", 1.0), "a\nb");
+    }
+}
@@ -7,6 +7,8 @@ mod rot;
 mod vector;
 mod pose;
 
+pub mod input;
+
 pub use crypto::*;
 pub use extensions::*;
 pub use grid::*;
@@ -23,27 +25,33 @@ pub use std::cmp;
 pub use std::cmp::{max, min, Ordering, Reverse};
 pub use std::collections::{BTreeMap, BTreeSet, BinaryHeap, HashMap, HashSet, VecDeque};
 
+/// Embeds the puzzle input at `input/YYYY/DD.txt`, optionally prefixed with
+/// `extra`. The file is read at compile time so it must exist for the day to
+/// build, even when the runner swaps in another profile's input at run time.
 #[macro_export]
 macro_rules! include_input {
     ($extra:literal / $year:literal / $day:literal) => {{
-        concat!(
+        $crate::input::apply_with(
             $extra,
-            include_str!(concat!(
-                "../input/",
-                stringify!($year),
-                "/",
-                stringify!($day),
-                ".txt"
-            ))
+            concat!(
+                $extra,
+                include_str!(concat!(
+                    "../input/",
+                    stringify!($year),
+                    "/",
+                    stringify!($day),
+                    ".txt"
+                ))
+            ),
         )
     }};
     ($year:literal / $day:literal) => {{
-        include_str!(concat!(
+        $crate::input::apply(include_str!(concat!(
             "../input/",
             stringify!($year),
             "/",
             stringify!($day),
             ".txt"
-        ))
+        )))
     }};
 }
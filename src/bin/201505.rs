@@ -0,0 +1,2 @@
+// Generated by `cargo advent` from 2015/05.rs, edit that instead.
+include!("../../2015/05.rs");
@@ -0,0 +1,2 @@
+// Generated by `cargo advent` from 2015/18.rs, edit that instead.
+include!("../../2015/18.rs");
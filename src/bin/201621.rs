@@ -0,0 +1,2 @@
+// Generated by `cargo advent` from 2016/21.rs, edit that instead.
+include!("../../2016/21.rs");
@@ -0,0 +1,2 @@
+// Generated by `cargo advent` from 2017/10.rs, edit that instead.
+include!("../../2017/10.rs");
@@ -0,0 +1,2 @@
+// Generated by `cargo advent` from 2022/12.rs, edit that instead.
+include!("../../2022/12.rs");
@@ -0,0 +1,2 @@
+// Generated by `cargo advent` from 2023/11.rs, edit that instead.
+include!("../../2023/11.rs");
@@ -0,0 +1,45 @@
+// Day { day }: { title }
+//
+// The input is { shape }.
+
+use advent::prelude::*;
+
+fn default_input() -> &'static str {
+    include_input!({ year } / { day })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Cell {
+    Empty,
+    Wall,
+}
+
+fn parse_cell(c: char) -> Cell {
+    match c {
+        '#' => Cell::Wall,
+        _ => Cell::Empty,
+    }
+}
+
+fn parse_grid(input: &str) -> Grid<Cell> {
+    Grid::new_by_char(input, parse_cell)
+}
+
+fn part1(input: &str) -> i64 {
+    let grid = parse_grid(input);
+    todo!("Part 1")
+}
+
+fn part2(input: &str) -> i64 {
+    let grid = parse_grid(input);
+    todo!("Part 2")
+}
+
+fn main() {
+    let solution = advent::new(default_input).part(part1).part(part2).build();
+    solution.cli();
+}
+
+{ examples }
+
+advent::answers!(default_input, { year } / { day }, part1, part2);
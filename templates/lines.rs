@@ -0,0 +1,36 @@
+// Day { day }: { title }
+//
+// The input is { shape }.
+
+use advent::prelude::*;
+
+fn default_input() -> &'static str {
+    include_input!({ year } / { day })
+}
+
+fn parse_line(line: &str) -> Vec<i64> {
+    line.nums().collect()
+}
+
+fn parse(input: &str) -> Vec<Vec<i64>> {
+    input.lines().map(parse_line).collect()
+}
+
+fn part1(input: &str) -> i64 {
+    let lines = parse(input);
+    todo!("Part 1")
+}
+
+fn part2(input: &str) -> i64 {
+    let lines = parse(input);
+    todo!("Part 2")
+}
+
+fn main() {
+    let solution = advent::new(default_input).part(part1).part(part2).build();
+    solution.cli();
+}
+
+{ examples }
+
+advent::answers!(default_input, { year } / { day }, part1, part2);
@@ -0,0 +1,80 @@
+// Day { day }: { title }
+//
+// The input is { shape }.
+
+use advent::prelude::*;
+
+fn default_input() -> &'static str {
+    include_input!({ year } / { day })
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Instruction {
+    Nop,
+    Add(i64),
+    Jmp(i64),
+}
+
+fn parse_instruction(line: &str) -> Instruction {
+    let arg = line.nums().next().unwrap_or_default();
+    match line.split_whitespace().next() {
+        Some("add") => Instruction::Add(arg),
+        Some("jmp") => Instruction::Jmp(arg),
+        _ => Instruction::Nop,
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Machine {
+    acc: i64,
+    ip: i64,
+    instructions: Vec<Instruction>,
+}
+
+impl Machine {
+    fn new(input: &str) -> Machine {
+        Machine {
+            acc: 0,
+            ip: 0,
+            instructions: input.lines().map(parse_instruction).collect(),
+        }
+    }
+
+    /// Runs one instruction, returning false once the program has halted.
+    fn step(&mut self) -> bool {
+        let Some(&instruction) = self.instructions.get(self.ip as usize) else {
+            return false;
+        };
+        match instruction {
+            Instruction::Nop => {}
+            Instruction::Add(n) => self.acc += n,
+            Instruction::Jmp(off) => self.ip += off - 1,
+        }
+        self.ip += 1;
+        true
+    }
+
+    fn run(&mut self) {
+        while self.step() {}
+    }
+}
+
+fn part1(input: &str) -> i64 {
+    let mut machine = Machine::new(input);
+    machine.run();
+    todo!("Part 1")
+}
+
+fn part2(input: &str) -> i64 {
+    let mut machine = Machine::new(input);
+    todo!("Part 2")
+}
+
+fn main() {
+    let solution = advent::new(default_input).part(part1).part(part2).build();
+    solution.cli();
+}
+
+{ examples }
+
+advent::answers!(default_input, { year } / { day }, part1, part2);